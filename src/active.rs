@@ -0,0 +1,103 @@
+// Active-region ticking for mostly empty boards
+//
+// A cell can only change if it or one of its neighbors changed on the
+// previous tick; otherwise the rule sees exactly the same inputs as last
+// time and gives the same answer. With active ticking on, the universe
+// remembers those cells and `tick` revisits only them instead of scanning
+// the whole grid, so a lone glider on a huge board costs a few dozen cell
+// updates per tick.
+
+use crate::{Automaton, Universe};
+use fixedbitset::FixedBitSet;
+use wasm_bindgen::prelude::*;
+
+impl Universe {
+    // Active ticking only covers plain two-state Life: dying cells and
+    // Wireworld wires change on their own, without any neighbor changing
+    pub(crate) fn can_tick_active(&self) -> bool {
+        self.active_ticking
+            && !self.all_active
+            && self.automaton == Automaton::Life
+            && self.states == 2
+    }
+
+    // Like `tick_all` but only for the cells in the active set. Every other
+    // cell keeps its state, and live ones still age.
+    pub(crate) fn tick_active(&mut self, next: &mut FixedBitSet) -> u32 {
+        next.clone_from(&self.cells);
+        let mut population = self.population;
+        let active: Vec<usize> = self.active.ones().collect();
+        for idx in active {
+            let (row, col) = self.get_coords(idx);
+            let cell = self.cell(idx);
+            let next_cell = self.next_cell(row, col);
+            let next_cell = self.settle_cell(idx, row, col, next_cell);
+            if next_cell != cell {
+                next.set(idx, next_cell.is_alive());
+                self.changed.push(row * self.width + col);
+                if next_cell.is_alive() {
+                    population += 1;
+                } else {
+                    population -= 1;
+                    self.ages[idx] = 0;
+                }
+            }
+        }
+        // Surviving cells age by one, newly born cells start at 1
+        for idx in next.ones() {
+            self.ages[idx] += 1;
+        }
+        // Report the flipped cells row by row, as a full scan would
+        self.changed.sort_unstable();
+        population
+    }
+
+    // Rebuilds the active set from the cells that just flipped
+    pub(crate) fn update_active_set(&mut self) {
+        if self.automaton != Automaton::Life || self.states != 2 {
+            self.all_active = true;
+            return;
+        }
+        self.all_active = false;
+        self.active = FixedBitSet::with_capacity(self.cells.len());
+        for i in 0..self.changed.len() {
+            let (row, col) = (self.changed[i] / self.width, self.changed[i] % self.width);
+            self.mark_active(self.get_index(row, col));
+        }
+    }
+
+    // Adds a cell and its neighbors to the active set after it changed
+    pub(crate) fn mark_active(&mut self, idx: usize) {
+        if !self.active_ticking || self.all_active {
+            return;
+        }
+        let (row, col) = self.get_coords(idx);
+        let mut active = std::mem::take(&mut self.active);
+        active.insert(idx);
+        self.for_each_neighbor(row, col, |neighbor| active.insert(neighbor));
+        self.active = active;
+    }
+
+    // Makes the next tick visit every cell, after a change that can affect
+    // any of them (a new rule, topology or neighborhood, or a new board)
+    pub(crate) fn wake_all(&mut self) {
+        self.all_active = true;
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    pub fn active_ticking(&self) -> bool {
+        self.active_ticking
+    }
+
+    // Makes `tick` revisit only the cells that changed on the previous tick
+    // and their neighbors, which speeds up sparse boards a lot while giving
+    // exactly the same results. Busy boards gain nothing from it, and it
+    // falls back to full scans for Generations rules and Wireworld. Off by
+    // default.
+    pub fn set_active_ticking(&mut self, enabled: bool) {
+        self.active_ticking = enabled;
+        self.wake_all();
+    }
+}
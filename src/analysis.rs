@@ -0,0 +1,361 @@
+// Queries that analyze the board: still lifes, oscillators, fingerprints,
+// symmetry, ...
+
+use crate::history::Snapshot;
+use crate::{reorder, Automaton, Bounds, GameError, Stats, StorageOrder, Universe};
+use fixedbitset::FixedBitSet;
+use std::collections::VecDeque;
+use wasm_bindgen::prelude::*;
+
+// Longest oscillator period `tick_until_stable` recognizes
+const MAX_SETTLE_PERIOD: usize = 32;
+
+// The four phases of a south-east moving glider, each filling a 3x3 box
+const GLIDER_PHASES: [[&str; 3]; 4] = [
+    [".O.", "..O", "OOO"],
+    ["O.O", ".OO", ".O."],
+    ["..O", "O.O", ".OO"],
+    ["O..", ".OO", "OO."],
+];
+
+// Every glider phase in every orientation as a 9-bit mask, bit `3 * row +
+// col` set for a live cell. Rotating and mirroring the phases covers
+// gliders moving in all four diagonal directions.
+fn glider_masks() -> Vec<u16> {
+    let rotate = |mask: u16| -> u16 {
+        (0..9)
+            .filter(|bit| mask & (1 << bit) != 0)
+            .map(|bit| 1 << (3 * (bit % 3) + (2 - bit / 3)))
+            .sum()
+    };
+    let mirror = |mask: u16| -> u16 {
+        (0..9)
+            .filter(|bit| mask & (1 << bit) != 0)
+            .map(|bit| 1 << (3 * (bit / 3) + (2 - bit % 3)))
+            .sum()
+    };
+
+    let mut masks = Vec::new();
+    for phase in GLIDER_PHASES.iter() {
+        let mut mask: u16 = 0;
+        for (row, line) in phase.iter().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                if ch == 'O' {
+                    mask |= 1 << (3 * row + col);
+                }
+            }
+        }
+        for _ in 0..4 {
+            for candidate in [mask, mirror(mask)].iter() {
+                if !masks.contains(candidate) {
+                    masks.push(*candidate);
+                }
+            }
+            mask = rotate(mask);
+        }
+    }
+    masks
+}
+
+#[wasm_bindgen]
+impl Universe {
+    // True when the next generation would be identical to the current one,
+    // i.e. the board is a still life (an empty board counts as stable).
+    // Uses the same transition as `tick`, so immortal cells never count as
+    // changing, and dying cells or Wireworld tails still on their way out
+    // keep the board unstable.
+    pub fn is_stable(&self) -> bool {
+        let mut next = Vec::new();
+        self.tick_into(&mut next);
+        (0..self.height).all(|row| {
+            (0..self.width).all(|col| {
+                let idx = self.get_index(row, col);
+                next[(row * self.width + col) as usize] == self.cell(idx) && self.state_settled(idx)
+            })
+        })
+    }
+
+    // Shortest period, up to `max_period` ticks, after which the board returns
+    // to its current state: 1 for a still life, 2 for a blinker, and so on.
+    // Returns None if the board doesn't repeat within `max_period` ticks.
+    pub fn is_periodic(&self, max_period: u32) -> Option<u32> {
+        // The copy must keep its size for its cells to line up with these,
+        // and has no use for undo history
+        let mut future = self.clone();
+        future.auto_expand = false;
+        future.history.clear();
+        future.history_depth = 0;
+        for period in 1..=max_period {
+            future.tick();
+            if future.cells == self.cells {
+                return Some(period);
+            }
+        }
+        None
+    }
+
+    // Cheap fingerprint of the board for period detection and caching. Boards
+    // with identical dimensions and cells always hash the same, on every
+    // target and in either storage order: this is FNV-1a over the dimensions
+    // and the bit-packed cells in row-major order.
+    pub fn state_hash(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let mut hash = FNV_OFFSET;
+        let mut feed = |bytes: &[u8]| {
+            for &byte in bytes {
+                hash = (hash ^ byte as u64).wrapping_mul(FNV_PRIME);
+            }
+        };
+        feed(&self.width.to_le_bytes());
+        feed(&self.height.to_le_bytes());
+
+        // Blocks are 32 or 64 bits depending on the target, so hash exactly
+        // the bytes covering the cells to get the same result everywhere
+        let reordered;
+        let row_major = match self.order {
+            StorageOrder::RowMajor => &self.cells,
+            StorageOrder::ColumnMajor => {
+                reordered = reorder(
+                    &self.cells,
+                    self.width,
+                    self.height,
+                    self.order,
+                    StorageOrder::RowMajor,
+                );
+                &reordered
+            }
+        };
+        let mut remaining = row_major.len().div_ceil(8);
+        for block in row_major.as_slice() {
+            let bytes = block.to_le_bytes();
+            let take = remaining.min(bytes.len());
+            feed(&bytes[..take]);
+            remaining -= take;
+        }
+        hash
+    }
+
+    // Population, generation, bounding box and stability in one call. The
+    // population and bounding box come from a single scan of the live cells.
+    pub fn stats(&self) -> Stats {
+        let mut population = 0;
+        let mut bounds: Option<Bounds> = None;
+        for (row, col) in self.live_cells_iter() {
+            population += 1;
+            bounds = Some(match bounds {
+                None => Bounds {
+                    min_row: row,
+                    min_col: col,
+                    max_row: row,
+                    max_col: col,
+                },
+                Some(b) => Bounds {
+                    min_row: b.min_row.min(row),
+                    min_col: b.min_col.min(col),
+                    max_row: b.max_row.max(row),
+                    max_col: b.max_col.max(col),
+                },
+            });
+        }
+        Stats {
+            population,
+            generation: self.generation,
+            bounds,
+            stable: self.is_stable(),
+        }
+    }
+
+    // True when the board equals its left-to-right mirror image (the result
+    // of `flip_horizontal`). With an odd width the middle column maps onto
+    // itself.
+    pub fn is_symmetric_horizontal(&self) -> bool {
+        (0..self.height).all(|row| {
+            (0..self.width / 2).all(|col| {
+                self.cells[self.get_index(row, col)]
+                    == self.cells[self.get_index(row, self.width - 1 - col)]
+            })
+        })
+    }
+
+    // True when the board equals its top-to-bottom mirror image (the result
+    // of `flip_vertical`). With an odd height the middle row maps onto itself.
+    pub fn is_symmetric_vertical(&self) -> bool {
+        (0..self.height / 2).all(|row| {
+            (0..self.width).all(|col| {
+                self.cells[self.get_index(row, col)]
+                    == self.cells[self.get_index(self.height - 1 - row, col)]
+            })
+        })
+    }
+
+    // True when the board is unchanged by mirroring across its main diagonal,
+    // i.e. cell (r, c) always matches (c, r). Only square boards can be.
+    pub fn is_symmetric_diagonal(&self) -> bool {
+        self.width == self.height
+            && (0..self.height).all(|row| {
+                (0..row).all(|col| {
+                    self.cells[self.get_index(row, col)] == self.cells[self.get_index(col, row)]
+                })
+            })
+    }
+
+    // Ticks until the board settles into a still life or an oscillator with a
+    // period up to 32, e.g. for a "run to completion" button. Returns the
+    // number of ticks taken, or `max_ticks` if the board hasn't settled by
+    // then. Oscillators are spotted by a repeated `state_hash` (plus the
+    // dying and Wireworld states, if any), so the board stops once it has
+    // gone through a full period.
+    pub fn tick_until_stable(&mut self, max_ticks: u32) -> u32 {
+        let mut recent: VecDeque<u64> = VecDeque::with_capacity(MAX_SETTLE_PERIOD);
+        recent.push_back(self.settle_hash());
+        for ticks in 0..max_ticks {
+            if self.is_stable() {
+                return ticks;
+            }
+            self.tick();
+
+            let hash = self.settle_hash();
+            if recent.contains(&hash) {
+                return ticks + 1;
+            }
+            if recent.len() == MAX_SETTLE_PERIOD {
+                recent.pop_front();
+            }
+            recent.push_back(hash);
+        }
+        max_ticks
+    }
+
+    // Approximate memory used by the universe in bytes: the struct itself
+    // plus its cells, scratch and per-cell buffers, undo history,
+    // checkpoints and population history. Grows linearly with
+    // `width * height`.
+    pub fn memory_bytes(&self) -> usize {
+        use std::mem::size_of;
+
+        let bitset_bytes = |bits: &FixedBitSet| std::mem::size_of_val(bits.as_slice());
+        let snapshots: usize = self
+            .history
+            .iter()
+            .chain(self.checkpoints.iter())
+            .map(|snapshot| size_of::<Snapshot>() + snapshot.heap_bytes())
+            .sum();
+        #[cfg(feature = "parallel")]
+        let parallel_bytes = self.next_cells.capacity() * size_of::<crate::Cell>();
+        #[cfg(not(feature = "parallel"))]
+        let parallel_bytes = 0;
+
+        size_of::<Universe>()
+            + bitset_bytes(&self.cells)
+            + bitset_bytes(&self.next)
+            + bitset_bytes(&self.immortal)
+            + bitset_bytes(&self.active)
+            + self.changed.capacity() * size_of::<u32>()
+            + self.ages.capacity() * size_of::<u32>()
+            + self.decay.capacity() * size_of::<u8>()
+            + self.neighbor_counts.capacity() * size_of::<u8>()
+            + self.population_history.capacity() * size_of::<u32>()
+            + snapshots
+            + parallel_bytes
+    }
+
+    // Estimates the number of gliders on the board by matching every glider
+    // phase and orientation against each 3x3 window, wrapping around the
+    // edges. A match only counts when the ring of cells around the window is
+    // dead, so each glider is counted once and fragments of bigger patterns
+    // are ignored. Boards smaller than 5x5 never contain one.
+    pub fn count_gliders(&self) -> u32 {
+        if self.width < 5 || self.height < 5 {
+            return 0;
+        }
+
+        let masks = glider_masks();
+        let (width, height) = (self.width as i64, self.height as i64);
+        let alive = |row: i64, col: i64| {
+            self.cells[self.get_index(row.rem_euclid(height) as u32, col.rem_euclid(width) as u32)]
+        };
+
+        let mut count = 0;
+        for row in 0..height {
+            for col in 0..width {
+                // Read the 3x3 window starting here into a mask
+                let mut window: u16 = 0;
+                for bit in 0..9 {
+                    if alive(row + bit / 3, col + bit % 3) {
+                        window |= 1 << bit;
+                    }
+                }
+                if !masks.contains(&window) {
+                    continue;
+                }
+
+                let ring_dead = (-1..=3).all(|dr| {
+                    (-1..=3).all(|dc| {
+                        let inside = (0..3).contains(&dr) && (0..3).contains(&dc);
+                        inside || !alive(row + dr, col + dc)
+                    })
+                });
+                if ring_dead {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    // Number of cells that differ between this board and `other` (the
+    // Hamming distance), e.g. to measure how far two runs with different
+    // rules have drifted apart. Compares cell by cell, whatever the storage
+    // order of each board. Fails if the dimensions differ.
+    pub fn difference(&self, other: &Universe) -> Result<u32, GameError> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return Err(GameError::SizeMismatch {
+                width: self.width,
+                height: self.height,
+                other_width: other.width,
+                other_height: other.height,
+            });
+        }
+        let count = if self.order == other.order {
+            self.cells.symmetric_difference_count(&other.cells)
+        } else {
+            let theirs = reorder(
+                &other.cells,
+                self.width,
+                self.height,
+                other.order,
+                self.order,
+            );
+            self.cells.symmetric_difference_count(&theirs)
+        };
+        Ok(count as u32)
+    }
+}
+
+impl Universe {
+    // Whether a tick leaves the dying or Wireworld state of the cell at `idx`
+    // alone
+    fn state_settled(&self, idx: usize) -> bool {
+        self.immortal.contains(idx)
+            || match self.automaton {
+                Automaton::Life => self.decay[idx] == 0,
+                Automaton::Wireworld => self.wireworld_state_settled(idx),
+            }
+    }
+
+    // `state_hash` extended with the per-cell states of Generations rules and
+    // Wireworld, which the live cells alone don't capture
+    fn settle_hash(&self) -> u64 {
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let mut hash = self.state_hash();
+        if self.states > 2 {
+            for &state in &self.decay {
+                hash = (hash ^ state as u64).wrapping_mul(FNV_PRIME);
+            }
+        }
+        hash
+    }
+}
@@ -0,0 +1,90 @@
+// Builder for configuring a universe in one expression instead of a
+// constructor followed by several setter calls
+
+use crate::rule::Rule;
+use crate::{validate_dimensions, GameError, Topology, Universe};
+use wasm_bindgen::prelude::*;
+
+// Settings for a new universe. Each method consumes the builder and returns
+// it, so calls chain from Rust and JS alike:
+// `UniverseBuilder::new().width(32).height(32).rule("B36/S23").build()`
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct UniverseBuilder {
+    width: u32,
+    height: u32,
+    rule: String,
+    topology: Topology,
+    seed: Option<u64>,
+    density: f64,
+}
+
+impl Default for UniverseBuilder {
+    fn default() -> UniverseBuilder {
+        UniverseBuilder::new()
+    }
+}
+
+#[wasm_bindgen]
+impl UniverseBuilder {
+    // Starts from the same settings as `Universe::new()`: 64x64, Conway's
+    // rule, wrapping edges and the fixed starting pattern
+    pub fn new() -> UniverseBuilder {
+        UniverseBuilder {
+            width: 64,
+            height: 64,
+            rule: Rule::CONWAY.to_string(),
+            topology: Topology::Toroidal,
+            seed: None,
+            density: 0.5,
+        }
+    }
+
+    pub fn width(mut self, width: u32) -> UniverseBuilder {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: u32) -> UniverseBuilder {
+        self.height = height;
+        self
+    }
+
+    // Rule in B/S notation, e.g. "B36/S23" for HighLife. Checked by `build`.
+    pub fn rule(mut self, rule: &str) -> UniverseBuilder {
+        self.rule = rule.to_string();
+        self
+    }
+
+    pub fn topology(mut self, topology: Topology) -> UniverseBuilder {
+        self.topology = topology;
+        self
+    }
+
+    // Fills the board like `randomize` instead of using the fixed starting
+    // pattern
+    pub fn seed(mut self, seed: u64) -> UniverseBuilder {
+        self.seed = Some(seed);
+        self
+    }
+
+    // Chance of each cell starting alive when a seed is given (0.5 by default)
+    pub fn density(mut self, density: f64) -> UniverseBuilder {
+        self.density = density;
+        self
+    }
+
+    // Creates the universe. Returns an error for invalid dimensions (zero,
+    // or too many cells) or a malformed rule.
+    pub fn build(&self) -> Result<Universe, GameError> {
+        validate_dimensions(self.width, self.height)?;
+
+        let mut universe = Universe::new_with_size(self.width, self.height);
+        universe.set_rule_string(&self.rule)?;
+        universe.set_topology(self.topology);
+        if let Some(seed) = self.seed {
+            universe.randomize(seed, self.density);
+        }
+        Ok(universe)
+    }
+}
@@ -0,0 +1,84 @@
+// Errors returned by fallible methods instead of panicking. A panic in wasm
+// surfaces in the browser as an opaque "unreachable executed", while an
+// `Err` becomes a JS exception carrying the message below.
+
+use std::fmt;
+use wasm_bindgen::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GameError {
+    // A (row, col) outside a `width` x `height` board
+    OutOfBounds {
+        row: u32,
+        col: u32,
+        width: u32,
+        height: u32,
+    },
+    // A buffer holding `actual` cells where the board needs `expected`
+    DimensionMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    // Two boards that need the same dimensions but don't have them
+    SizeMismatch {
+        width: u32,
+        height: u32,
+        other_width: u32,
+        other_height: u32,
+    },
+    // A board with a zero width or height, or too many cells to index
+    InvalidDimensions {
+        width: u32,
+        height: u32,
+    },
+    // Malformed text or binary input (rules, RLE, JSON, saved boards)
+    Parse(String),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::OutOfBounds {
+                row,
+                col,
+                width,
+                height,
+            } => write!(
+                f,
+                "cell ({}, {}) is outside the {}x{} universe",
+                row, col, width, height
+            ),
+            GameError::DimensionMismatch { expected, actual } => {
+                write!(f, "expected {} cells, got {}", expected, actual)
+            }
+            GameError::SizeMismatch {
+                width,
+                height,
+                other_width,
+                other_height,
+            } => write!(
+                f,
+                "universes differ in size: {}x{} and {}x{}",
+                width, height, other_width, other_height
+            ),
+            GameError::InvalidDimensions { width, height } => write!(
+                f,
+                "universe dimensions must be non-zero with at most {} cells, got {}x{}",
+                u32::MAX,
+                width,
+                height
+            ),
+            GameError::Parse(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+// Lets `#[wasm_bindgen]` methods return `Result<_, GameError>`, throwing the
+// message as a JS exception
+impl From<GameError> for JsValue {
+    fn from(error: GameError) -> JsValue {
+        JsValue::from_str(&error.to_string())
+    }
+}
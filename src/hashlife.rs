@@ -0,0 +1,366 @@
+// Hashlife: memoized quadtree simulation for huge, regular patterns
+//
+// The plane is a quadtree whose nodes are canonicalized, so every distinct
+// square of cells is stored once no matter how often it repeats. For each
+// node the result of running its center forward is cached, which lets
+// repetitive patterns be advanced by enormous numbers of generations at
+// once. See Gosper, "Exploiting regularities in large cellular spaces"
+// (1984).
+
+use crate::rule::Rule;
+use crate::{Cell, Pattern};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+type NodeId = u32;
+
+// The two leaves (single cells) are always the first two nodes
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+
+// Level of the root when the plane is created (an 8x8 square)
+const INITIAL_LEVEL: u8 = 3;
+
+// Largest k `advance` accepts: 2^63 ticks still fit the u64 generation
+// counter, and the root stays well within i128 coordinates
+const MAX_ADVANCE: u8 = 63;
+
+// A 2^level x 2^level square of cells made of four half-size quadrants.
+// Leaves (level 0) have no children.
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    level: u8,
+    children: [NodeId; 4], // nw, ne, sw, se
+    population: u64,
+}
+
+// An unbounded universe running Conway's B3/S23 with Hashlife. Coordinates
+// are signed like `SparseUniverse`. Nodes and cached results are never
+// freed, so memory grows with the variety of patterns seen; start a new
+// universe to reclaim it.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct HashlifeUniverse {
+    nodes: Vec<Node>,                       // Every node, indexed by NodeId
+    index: HashMap<[NodeId; 4], NodeId>,    // Canonical node for each set of children
+    results: HashMap<(NodeId, u8), NodeId>, // Center of a node after 2^j ticks, by (node, j)
+    empties: Vec<NodeId>,                   // The all-dead node at each level
+    root: NodeId,                           // Square centered on (0, 0) holding every live cell
+    generation: u64,                        // Number of ticks since creation
+}
+
+impl Default for HashlifeUniverse {
+    fn default() -> Self {
+        let leaf = |population| Node {
+            level: 0,
+            children: [DEAD; 4],
+            population,
+        };
+        let mut universe = HashlifeUniverse {
+            nodes: vec![leaf(0), leaf(1)],
+            index: HashMap::new(),
+            results: HashMap::new(),
+            empties: vec![DEAD],
+            root: DEAD,
+            generation: 0,
+        };
+        universe.root = universe.empty(INITIAL_LEVEL);
+        universe
+    }
+}
+
+impl HashlifeUniverse {
+    fn level(&self, node: NodeId) -> u8 {
+        self.nodes[node as usize].level
+    }
+
+    fn children(&self, node: NodeId) -> [NodeId; 4] {
+        self.nodes[node as usize].children
+    }
+
+    // The canonical node with the given quadrants, creating it if needed
+    fn join(&mut self, nw: NodeId, ne: NodeId, sw: NodeId, se: NodeId) -> NodeId {
+        let children = [nw, ne, sw, se];
+        if let Some(&node) = self.index.get(&children) {
+            return node;
+        }
+        let population = children
+            .iter()
+            .map(|&child| self.nodes[child as usize].population)
+            .sum();
+        let node = self.nodes.len() as NodeId;
+        self.nodes.push(Node {
+            level: self.level(nw) + 1,
+            children,
+            population,
+        });
+        self.index.insert(children, node);
+        node
+    }
+
+    // The all-dead node of the given level
+    fn empty(&mut self, level: u8) -> NodeId {
+        while self.empties.len() <= level as usize {
+            let e = self.empties[self.empties.len() - 1];
+            let bigger = self.join(e, e, e, e);
+            self.empties.push(bigger);
+        }
+        self.empties[level as usize]
+    }
+
+    // Doubles the root's size, keeping its cells centered on (0, 0)
+    fn expand(&mut self) {
+        let [nw, ne, sw, se] = self.children(self.root);
+        let e = self.empty(self.level(self.root) - 1);
+        let nw = self.join(e, e, e, nw);
+        let ne = self.join(e, e, ne, e);
+        let sw = self.join(e, sw, e, e);
+        let se = self.join(se, e, e, e);
+        self.root = self.join(nw, ne, sw, se);
+    }
+
+    // The half-size square at the center of `node`
+    fn center(&mut self, node: NodeId) -> NodeId {
+        let [nw, ne, sw, se] = self.children(node);
+        let (nw, ne) = (self.children(nw)[3], self.children(ne)[2]);
+        let (sw, se) = (self.children(sw)[1], self.children(se)[0]);
+        self.join(nw, ne, sw, se)
+    }
+
+    // The square straddling the seam between two side-by-side nodes
+    fn center_horizontal(&mut self, west: NodeId, east: NodeId) -> NodeId {
+        let [_, w_ne, _, w_se] = self.children(west);
+        let [e_nw, _, e_sw, _] = self.children(east);
+        self.join(w_ne, e_nw, w_se, e_sw)
+    }
+
+    // The square straddling the seam between two stacked nodes
+    fn center_vertical(&mut self, north: NodeId, south: NodeId) -> NodeId {
+        let [_, _, n_sw, n_se] = self.children(north);
+        let [s_nw, s_ne, _, _] = self.children(south);
+        self.join(n_sw, n_se, s_nw, s_ne)
+    }
+
+    // Runs a 4x4 node one tick the slow way, returning its central 2x2
+    fn step_base(&mut self, node: NodeId) -> NodeId {
+        let mut grid = [[false; 4]; 4];
+        for (quadrant, &child) in self.children(node).iter().enumerate() {
+            for (i, &leaf) in self.children(child).iter().enumerate() {
+                let row = quadrant / 2 * 2 + i / 2;
+                let col = quadrant % 2 * 2 + i % 2;
+                grid[row][col] = leaf == ALIVE;
+            }
+        }
+
+        let mut next = [DEAD; 4];
+        for (i, cell) in next.iter_mut().enumerate() {
+            let (row, col) = (1 + i / 2, 1 + i % 2);
+            // Count the live cells in the 3x3 block, minus the cell itself
+            let block = grid[row - 1..=row + 1]
+                .iter()
+                .flat_map(|line| &line[col - 1..=col + 1])
+                .filter(|&&alive| alive)
+                .count() as u8;
+            let neighbors = block - grid[row][col] as u8;
+            let state = Rule::CONWAY.next_cell(Cell::from(grid[row][col]), neighbors);
+            *cell = if state.is_alive() { ALIVE } else { DEAD };
+        }
+        self.join(next[0], next[1], next[2], next[3])
+    }
+
+    // The center of `node` (half its size) after 2^j ticks, where j is at
+    // most the node's level minus 2
+    fn step(&mut self, node: NodeId, j: u8) -> NodeId {
+        let level = self.level(node);
+        if self.nodes[node as usize].population == 0 {
+            return self.empty(level - 1);
+        }
+        if let Some(&result) = self.results.get(&(node, j)) {
+            return result;
+        }
+
+        let result = if level == 2 {
+            self.step_base(node)
+        } else {
+            // Nine overlapping quarter-size squares tiling the node
+            let [nw, ne, sw, se] = self.children(node);
+            let n = self.center_horizontal(nw, ne);
+            let w = self.center_vertical(nw, sw);
+            let c = self.center(node);
+            let e = self.center_vertical(ne, se);
+            let s = self.center_horizontal(sw, se);
+            let squares = [nw, n, ne, w, c, e, sw, s, se];
+
+            // A full step runs each of them (and then the four squares built
+            // from their results) for half the time. Shorter steps only take
+            // their centers and spend all the time in the second round.
+            let full = j == level - 2;
+            let mut parts = [DEAD; 9];
+            for (part, &square) in parts.iter_mut().zip(&squares) {
+                *part = if full {
+                    self.step(square, level - 3)
+                } else {
+                    self.center(square)
+                };
+            }
+            let second = if full { level - 3 } else { j };
+
+            let quads = [
+                self.join(parts[0], parts[1], parts[3], parts[4]),
+                self.join(parts[1], parts[2], parts[4], parts[5]),
+                self.join(parts[3], parts[4], parts[6], parts[7]),
+                self.join(parts[4], parts[5], parts[7], parts[8]),
+            ];
+            let mut next = [DEAD; 4];
+            for (quad, &square) in next.iter_mut().zip(&quads) {
+                *quad = self.step(square, second);
+            }
+            self.join(next[0], next[1], next[2], next[3])
+        };
+
+        self.results.insert((node, j), result);
+        result
+    }
+
+    // Half the side length of the root, i.e. the root covers [-half, half)
+    fn half(&self) -> i128 {
+        1 << (self.level(self.root) - 1)
+    }
+
+    // Rebuilds `node` with the cell at (row, col), relative to its top-left
+    // corner, set to `alive`
+    fn set(&mut self, node: NodeId, row: i128, col: i128, alive: bool) -> NodeId {
+        let level = self.level(node);
+        if level == 0 {
+            return if alive { ALIVE } else { DEAD };
+        }
+        let half = 1 << (level - 1);
+        let quadrant = (row >= half) as usize * 2 + (col >= half) as usize;
+        let mut children = self.children(node);
+        children[quadrant] = self.set(children[quadrant], row % half, col % half, alive);
+        self.join(children[0], children[1], children[2], children[3])
+    }
+
+    // Appends the live cells of `node`, whose top-left corner is at
+    // (top, left), to `out`
+    fn collect(&self, node: NodeId, top: i128, left: i128, out: &mut Vec<(i64, i64)>) {
+        let Node {
+            level,
+            children,
+            population,
+        } = self.nodes[node as usize];
+        if population == 0 {
+            return;
+        }
+        if level == 0 {
+            out.push((top as i64, left as i64));
+            return;
+        }
+        let half = 1 << (level - 1);
+        for (quadrant, &child) in children.iter().enumerate() {
+            let row = top + half * (quadrant / 2) as i128;
+            let col = left + half * (quadrant % 2) as i128;
+            self.collect(child, row, col, out);
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl HashlifeUniverse {
+    // Creates an empty plane
+    pub fn new() -> HashlifeUniverse {
+        HashlifeUniverse::default()
+    }
+
+    // Advances the plane by one tick
+    pub fn tick(&mut self) {
+        self.advance(0);
+    }
+
+    // Advances the plane by 2^k ticks in one go, e.g. `advance(10)` for 1024
+    // generations. The larger k, the more the cached results pay off. k is
+    // capped at 63, so larger values advance by 2^63 ticks.
+    pub fn advance(&mut self, k: u8) {
+        let k = k.min(MAX_ADVANCE);
+        // The root is stepped for 2^(level - 2) ticks at most, and the
+        // pattern must keep clear of its edges for that long
+        loop {
+            let center = self.center(self.root);
+            let contained =
+                self.nodes[center as usize].population == self.nodes[self.root as usize].population;
+            if self.level(self.root) >= k + 2 && contained {
+                break;
+            }
+            self.expand();
+        }
+        self.expand();
+        self.root = self.step(self.root, k);
+        self.generation = self.generation.saturating_add(1 << k);
+    }
+
+    // Advances the plane by `n` ticks, as a series of power-of-two steps
+    pub fn tick_n(&mut self, n: u64) {
+        for k in 0..64 {
+            if n & (1 << k) != 0 {
+                self.advance(k);
+            }
+        }
+    }
+
+    pub fn population(&self) -> u64 {
+        self.nodes[self.root as usize].population
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn get_cell(&self, row: i64, col: i64) -> Cell {
+        let half = self.half();
+        let (mut row, mut col) = (row as i128 + half, col as i128 + half);
+        if row < 0 || col < 0 || row >= 2 * half || col >= 2 * half {
+            return Cell::Dead;
+        }
+        let mut node = self.root;
+        while self.level(node) > 0 {
+            let half = 1 << (self.level(node) - 1);
+            let quadrant = (row >= half) as usize * 2 + (col >= half) as usize;
+            node = self.children(node)[quadrant];
+            row %= half;
+            col %= half;
+        }
+        Cell::from(node == ALIVE)
+    }
+
+    pub fn set_cell(&mut self, row: i64, col: i64, cell: Cell) {
+        let (row, col) = (row as i128, col as i128);
+        while !(-self.half()..self.half()).contains(&row)
+            || !(-self.half()..self.half()).contains(&col)
+        {
+            self.expand();
+        }
+        let half = self.half();
+        self.root = self.set(self.root, row + half, col + half, cell.is_alive());
+    }
+
+    // Stamps a built-in pattern with its bounding box's top-left corner at
+    // (row, col), like `SparseUniverse::insert_pattern`
+    pub fn insert_pattern(&mut self, pattern: Pattern, row: i64, col: i64) {
+        for (delta_row, delta_col) in pattern.offsets() {
+            self.set_cell(row + delta_row as i64, col + delta_col as i64, Cell::Alive);
+        }
+    }
+
+    // Coordinates of the live cells as flattened (row, col) pairs, sorted
+    // row by row
+    pub fn live_cells(&self) -> Vec<i64> {
+        let mut cells = Vec::new();
+        let half = self.half();
+        self.collect(self.root, -half, -half, &mut cells);
+        cells.sort_unstable();
+        cells
+            .into_iter()
+            .flat_map(|(row, col)| [row, col])
+            .collect()
+    }
+}
@@ -0,0 +1,173 @@
+// Undo history, saved checkpoints and the population recorded each tick
+
+use crate::{StorageOrder, Universe};
+use fixedbitset::FixedBitSet;
+use std::collections::VecDeque;
+use wasm_bindgen::prelude::*;
+
+// How many snapshots a new universe keeps for `undo`
+pub(crate) const DEFAULT_HISTORY_DEPTH: usize = 16;
+
+// A copy of the board taken before it was changed. The dimensions are kept
+// alongside the cells so a snapshot from before a resize still restores, and
+// the dying and Wireworld states so undo doesn't erase them.
+#[derive(Clone, Debug)]
+pub(crate) struct Snapshot {
+    width: u32,
+    height: u32,
+    cells: FixedBitSet,
+    decay: Vec<u8>,
+    generation: u64,
+    order: StorageOrder,
+}
+
+// Snapshots in the order they were taken, oldest first
+pub(crate) type History = VecDeque<Snapshot>;
+
+impl Snapshot {
+    // Bytes held on the heap by the snapshot's cells and states
+    pub(crate) fn heap_bytes(&self) -> usize {
+        std::mem::size_of_val(self.cells.as_slice()) + self.decay.capacity()
+    }
+}
+
+impl Universe {
+    pub(crate) fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.clone(),
+            decay: self.decay.clone(),
+            generation: self.generation,
+            order: self.order,
+        }
+    }
+
+    // Overwrites an old snapshot with the current board, reusing its buffers
+    fn snapshot_into(&self, snapshot: &mut Snapshot) {
+        snapshot.width = self.width;
+        snapshot.height = self.height;
+        snapshot.cells.clone_from(&self.cells);
+        snapshot.decay.clone_from(&self.decay);
+        snapshot.generation = self.generation;
+        snapshot.order = self.order;
+    }
+
+    pub(crate) fn restore(&mut self, snapshot: Snapshot) {
+        self.width = snapshot.width;
+        self.height = snapshot.height;
+        self.cells = snapshot.cells;
+        self.generation = snapshot.generation;
+        self.order = snapshot.order;
+        self.reset_tracking_with_decay(snapshot.decay);
+        // The rule may have lost states since the snapshot was taken
+        self.clamp_decay();
+    }
+
+    // Saves the current board before a change. Once the history is full the
+    // oldest snapshot is overwritten, so ticking doesn't allocate a new board
+    // every generation.
+    pub(crate) fn record_history(&mut self) {
+        if self.history_depth == 0 {
+            return;
+        }
+        let oldest = if self.history.len() == self.history_depth {
+            self.history.pop_front()
+        } else {
+            None
+        };
+        let snapshot = match oldest {
+            Some(mut snapshot) => {
+                self.snapshot_into(&mut snapshot);
+                snapshot
+            }
+            None => self.snapshot(),
+        };
+        self.history.push_back(snapshot);
+    }
+
+    // Appends the current population to the history `tick` keeps for
+    // graphing
+    pub(crate) fn record_population(&mut self) {
+        let population = self.population();
+        self.population_history.push(population);
+        self.trim_population_history();
+    }
+
+    // Drops the oldest population entries past the limit
+    fn trim_population_history(&mut self) {
+        let limit = self.population_history_limit;
+        if limit > 0 && self.population_history.len() > limit {
+            let excess = self.population_history.len() - limit;
+            self.population_history.drain(..excess);
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    // Reverts the most recent tick or edit. Returns false when there is
+    // nothing left to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop_back() {
+            Some(snapshot) => {
+                self.restore(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Bookmarks the current board and returns a handle for
+    // `restore_checkpoint`. Checkpoints live until the universe is dropped.
+    pub fn save_checkpoint(&mut self) -> u32 {
+        let snapshot = self.snapshot();
+        self.checkpoints.push(snapshot);
+        (self.checkpoints.len() - 1) as u32
+    }
+
+    // Jumps back to a saved checkpoint (which can itself be undone). Returns
+    // false for unknown handles and for checkpoints taken at different
+    // dimensions than the current board.
+    pub fn restore_checkpoint(&mut self, handle: u32) -> bool {
+        let snapshot = match self.checkpoints.get(handle as usize) {
+            Some(snapshot) if snapshot.width == self.width && snapshot.height == self.height => {
+                snapshot.clone()
+            }
+            _ => return false,
+        };
+        self.record_history();
+        self.restore(snapshot);
+        true
+    }
+
+    pub fn history_depth(&self) -> u32 {
+        self.history_depth as u32
+    }
+
+    // Sets how many steps `undo` can go back (16 by default, 0 turns the
+    // history off). Shrinking it drops the oldest snapshots.
+    pub fn set_history_depth(&mut self, depth: u32) {
+        self.history_depth = depth as usize;
+        while self.history.len() > self.history_depth {
+            self.history.pop_front();
+        }
+    }
+
+    // Population after each tick since the last `clear`, `reset` or
+    // `clear_history`, oldest first, e.g. for charting growth over time.
+    pub fn population_history(&self) -> Vec<u32> {
+        self.population_history.clone()
+    }
+
+    pub fn clear_history(&mut self) {
+        self.population_history.clear();
+    }
+
+    // Caps the population history at `limit` entries, keeping the most
+    // recent ones. 0 (the default) keeps every tick.
+    pub fn set_population_history_limit(&mut self, limit: u32) {
+        self.population_history_limit = limit as usize;
+        self.trim_population_history();
+    }
+}
@@ -0,0 +1,84 @@
+// JSON save/load of the full universe state (requires the `serde` feature)
+
+use crate::{validate_dimensions, Cell, GameError, Universe};
+use fixedbitset::FixedBitSet;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+use wasm_bindgen::prelude::*;
+
+// The serialized form of a universe: its dimensions, generation and every
+// cell in row-major order as 0 (dead) or 1 (alive)
+#[derive(Serialize, Deserialize)]
+struct UniverseState {
+    width: u32,
+    height: u32,
+    generation: u64,
+    cells: Vec<Cell>,
+}
+
+impl From<&Universe> for UniverseState {
+    fn from(universe: &Universe) -> UniverseState {
+        UniverseState {
+            width: universe.width,
+            height: universe.height,
+            generation: universe.generation,
+            cells: (0..universe.height)
+                .flat_map(|row| (0..universe.width).map(move |col| (row, col)))
+                .map(|(row, col)| universe.cell(universe.get_index(row, col)))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<UniverseState> for Universe {
+    type Error = GameError;
+
+    fn try_from(state: UniverseState) -> Result<Universe, GameError> {
+        validate_dimensions(state.width, state.height)?;
+        let size = state.width as usize * state.height as usize;
+        if state.cells.len() != size {
+            return Err(GameError::DimensionMismatch {
+                expected: size,
+                actual: state.cells.len(),
+            });
+        }
+
+        let mut universe = Universe::new_with_size(state.width, state.height);
+        let mut cells = FixedBitSet::with_capacity(state.cells.len());
+        for (i, &cell) in state.cells.iter().enumerate() {
+            cells.set(i, cell.into());
+        }
+        universe.cells = cells;
+        universe.recount_population();
+        universe.generation = state.generation;
+        Ok(universe)
+    }
+}
+
+impl Serialize for Universe {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        UniverseState::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Universe {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Universe, D::Error> {
+        let state = UniverseState::deserialize(deserializer)?;
+        Universe::try_from(state).map_err(serde::de::Error::custom)
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    // Serializes the width, height, generation and cells to a JSON string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("universe state is always serializable")
+    }
+
+    // Restores a universe saved with `to_json`
+    pub fn from_json(json: &str) -> Result<Universe, GameError> {
+        let state: UniverseState =
+            serde_json::from_str(json).map_err(|e| GameError::Parse(e.to_string()))?;
+        Universe::try_from(state)
+    }
+}
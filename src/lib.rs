@@ -1,26 +1,339 @@
 // Declares a module named 'utils' - likely contains helper functions
 mod utils;
 
+// Birth/survival rule sets used by `tick`
+mod rule;
+use rule::Rule;
+pub use rule::RulePreset;
+
+// Chainable configuration of a new universe
+mod builder;
+pub use builder::UniverseBuilder;
+
+// Errors returned instead of panicking on bad input
+mod error;
+pub use error::GameError;
+
+// Built-in patterns for `insert_pattern`
+mod pattern;
+pub use pattern::Pattern;
+
+// Run Length Encoded pattern import
+mod rle;
+
+// Plaintext `.cells` pattern import and export
+mod plaintext;
+
+// Life 1.06 coordinate list import
+mod life106;
+
+// Rotations and reflections of the whole board
+mod transform;
+
+// Canvas and text renderers
+mod render;
+
+// Still-life and oscillator detection, board statistics
+mod analysis;
+
+// Unbounded universe storing only live cells
+mod sparse;
+pub use sparse::SparseUniverse;
+
+// Hashlife engine for huge, repetitive patterns
+mod hashlife;
+pub use hashlife::HashlifeUniverse;
+
+// Multi-state "Generations" rules with decaying cells
+mod generations;
+
+// Ticking only the cells near recent changes
+mod active;
+
+// apgsearch-compatible random soups
+mod soup;
+
+// Wireworld circuits
+mod wireworld;
+pub use wireworld::Automaton;
+
+// Undo history, checkpoints and population history
+mod history;
+use history::{History, Snapshot};
+
+// Compact binary save/load
+mod binary;
+
+// JSON save/load of the full universe state
+#[cfg(feature = "serde")]
+mod json;
+
 // Imports all public items from wasm_bindgen::prelude for WebAssembly bindings
 use wasm_bindgen::prelude::*;
 
+// Bit-packed storage: one bit per cell instead of a full byte
+use fixedbitset::FixedBitSet;
+
+// `TryFrom` isn't in the 2018 edition prelude
+use std::convert::TryFrom;
+
 // Marks this enum as exportable to JavaScript via WebAssembly
 #[wasm_bindgen]
 // Specifies the underlying representation as u8 (8-bit unsigned integer)
 #[repr(u8)]
 // Derives common traits: Clone (copy semantics), Copy (bitwise copy), Debug (formatting), PartialEq/Eq (equality comparison)
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+// With the `serde` feature, cells serialize as their numeric value (0 or 1)
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "u8", try_from = "u8")
+)]
 pub enum Cell {
     Dead = 0,  // Dead cell represented as 0
     Alive = 1, // Alive cell represented as 1
 }
 
+impl Cell {
+    // The opposite state: Alive for Dead and Dead for Alive
+    pub fn toggle(self) -> Cell {
+        match self {
+            Cell::Dead => Cell::Alive,
+            Cell::Alive => Cell::Dead,
+        }
+    }
+
+    pub fn is_alive(self) -> bool {
+        self == Cell::Alive
+    }
+}
+
+impl From<Cell> for u8 {
+    fn from(cell: Cell) -> u8 {
+        cell as u8
+    }
+}
+
+// Live cells are `true`, matching the bits in `cells()`
+impl From<bool> for Cell {
+    fn from(alive: bool) -> Cell {
+        if alive {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        }
+    }
+}
+
+impl From<Cell> for bool {
+    fn from(cell: Cell) -> bool {
+        cell.is_alive()
+    }
+}
+
+impl TryFrom<u8> for Cell {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Cell, String> {
+        match value {
+            0 => Ok(Cell::Dead),
+            1 => Ok(Cell::Alive),
+            _ => Err(format!("invalid cell value {}, expected 0 or 1", value)),
+        }
+    }
+}
+
+// How the edges of the grid behave when counting neighbors
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Topology {
+    Toroidal = 0, // Edges wrap around, so the grid behaves like a torus
+    Bounded = 1,  // Cells beyond the edges are permanently dead
+}
+
+// Which surrounding cells count as neighbors
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighborhood {
+    Moore = 0,      // All 8 surrounding cells, including diagonals
+    VonNeumann = 1, // Only the 4 orthogonally adjacent cells
+}
+
+// How cells are laid out in the bitset behind `cells()`
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageOrder {
+    RowMajor = 0,    // Cell (row, col) is at `row * width + col` (the default)
+    ColumnMajor = 1, // Cell (row, col) is at `col * height + row`
+}
+
+impl StorageOrder {
+    // Index of (row, col) on a `width` x `height` grid
+    fn index(self, width: u32, height: u32, row: u32, col: u32) -> usize {
+        match self {
+            StorageOrder::RowMajor => (row * width + col) as usize,
+            StorageOrder::ColumnMajor => (col * height + row) as usize,
+        }
+    }
+
+    // (row, col) of an index, the inverse of `index`
+    fn coords(self, width: u32, height: u32, idx: usize) -> (u32, u32) {
+        let idx = idx as u32;
+        match self {
+            StorageOrder::RowMajor => (idx / width, idx % width),
+            StorageOrder::ColumnMajor => (idx % height, idx / height),
+        }
+    }
+
+    // Distance between the indices of vertically and horizontally adjacent
+    // cells, as (row stride, column stride)
+    fn strides(self, width: u32, height: u32) -> (usize, usize) {
+        match self {
+            StorageOrder::RowMajor => (width as usize, 1),
+            StorageOrder::ColumnMajor => (1, height as usize),
+        }
+    }
+}
+
+// Copies a `width` x `height` grid of bits from one storage order to another
+fn reorder(
+    cells: &FixedBitSet,
+    width: u32,
+    height: u32,
+    from: StorageOrder,
+    to: StorageOrder,
+) -> FixedBitSet {
+    if from == to {
+        return cells.clone();
+    }
+    let mut reordered = FixedBitSet::with_capacity(cells.len());
+    for idx in cells.ones() {
+        let (row, col) = from.coords(width, height, idx);
+        reordered.insert(to.index(width, height, row, col));
+    }
+    reordered
+}
+
+// Smallest rectangle enclosing all live cells (inclusive on every side)
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub min_row: u32,
+    pub min_col: u32,
+    pub max_row: u32,
+    pub max_col: u32,
+}
+
+// Average position of the live cells, in fractional grid coordinates
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Centroid {
+    pub row: f64,
+    pub col: f64,
+}
+
+// Summary of the board returned by `stats`, so a UI can refresh all of its
+// readouts with one call
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub population: u32,
+    pub generation: u64,
+    pub bounds: Option<Bounds>, // None for an empty board
+    pub stable: bool,           // Same as `is_stable`
+}
+
+// Every constructor and resize goes through this check: a zero-sized grid
+// has no cells and would underflow the `size - 1` wrap offsets, and cell
+// indices are u32, so `width * height` must fit in one.
+fn validate_dimensions(width: u32, height: u32) -> Result<(), GameError> {
+    if width == 0 || height == 0 || width.checked_mul(height).is_none() {
+        return Err(GameError::InvalidDimensions { width, height });
+    }
+    Ok(())
+}
+
+// Panicking form of `validate_dimensions` for the methods that can't return
+// an error
+fn check_dimensions(width: u32, height: u32) {
+    if let Err(error) = validate_dimensions(width, height) {
+        panic!("{}", error);
+    }
+}
+
+// The starting board of `new()` and `reset`, in row-major order: every cell
+// whose flattened index is divisible by 2 or 7 is alive. Because the index
+// runs on from one row into the next, the stripes shift whenever the width
+// changes, e.g. an odd width staggers the even cells into a checkerboard.
+fn seed_cells(width: u32, height: u32) -> Vec<Cell> {
+    (0..(width * height) as usize)
+        .map(|i| Cell::from(i % 2 == 0 || i % 7 == 0))
+        .collect()
+}
+
+// The distinct signed offsets within `radius` rows (or columns) of a cell
+// on a torus `size` cells around. When the grid is narrower than the
+// neighborhood, offsets on either side wrap onto the same cell (or the cell
+// itself) and each one must only be counted once.
+fn wrap_deltas(size: u32, radius: u32) -> Vec<i64> {
+    let (size, radius) = (size as i64, radius as i64);
+    if size > 2 * radius {
+        (-radius..=radius).collect()
+    } else {
+        (0..size)
+            .map(|k| if k <= size / 2 { k } else { k - size })
+            .collect()
+    }
+}
+
+// Installs the panic hook so a panic shows its message and location in the
+// browser console instead of "unreachable executed". Call it once at
+// startup; calling it again is harmless.
+#[wasm_bindgen]
+pub fn init() {
+    utils::set_panic_hook();
+}
+
+// Largest neighborhood radius `set_neighbor_radius` accepts. A radius of 5
+// already gives 120 neighbors, the most a rule can track.
+const MAX_RADIUS: u32 = 5;
+
 // Marks this struct as exportable to JavaScript
 #[wasm_bindgen]
+#[derive(Clone, Debug)]
 pub struct Universe {
-    width: u32,       // Grid width in cells
-    height: u32,      // Grid height in cells
-    cells: Vec<Cell>, // 1D vector storing all cells (flattened 2D grid)
+    width: u32,         // Grid width in cells
+    height: u32,        // Grid height in cells
+    cells: FixedBitSet, // One bit per cell, set when alive (flattened 2D grid)
+    population: u32,    // Number of set bits in `cells`, kept in step with every edit
+    next: FixedBitSet,  // Scratch buffer the next generation is written into
+    #[cfg(feature = "parallel")]
+    next_cells: Vec<Cell>, // Scratch buffer rayon writes each cell's next state into
+    changed: Vec<u32>,  // Indices of the cells that flipped during the last tick
+    ages: Vec<u32>,     // Consecutive ticks each cell has been alive (0 when dead)
+    generation: u64,    // Number of ticks since creation or the last clear
+    topology: Topology, // Whether neighbor counting wraps around the edges
+    neighborhood: Neighborhood, // Which surrounding cells count as neighbors
+    radius: u32,        // How many cells away a neighbor can be
+    neighbor_counts: Vec<u8>, // Cached live-neighbor count per cell, empty unless enabled
+    active_ticking: bool, // Whether `tick` only revisits cells near recent changes
+    active: FixedBitSet, // Cells that may change on the next tick
+    all_active: bool,   // Whether every cell may change, overriding `active`
+    auto_expand: bool,  // Whether the board grows when live cells reach an edge
+    immortal: FixedBitSet, // Cells the rule never changes, e.g. walls
+    rule: Rule,         // Birth/survival counts applied on each tick
+    order: StorageOrder, // Layout of `cells` and the per-cell buffers
+    automaton: Automaton, // Life-like rules or Wireworld
+    states: u8,         // Cell states including dead and alive (2 for plain Life)
+    decay: Vec<u8>,     // Ticks each dying cell has left before it's dead
+    history: History,   // Earlier boards for `undo`, oldest first
+    history_depth: usize, // Most snapshots `history` keeps
+    checkpoints: Vec<Snapshot>, // Boards saved with `save_checkpoint`
+    population_history: Vec<u32>, // Population after each tick, oldest first
+    population_history_limit: usize, // Most entries kept (0 keeps them all)
 }
 
 // Implementation block for Universe
@@ -28,40 +341,361 @@ pub struct Universe {
 impl Universe {
     // Converts 2D coordinates to 1D vector index
     fn get_index(&self, row: u32, column: u32) -> usize {
-        (row * self.width + column) as usize // Row-major order indexing
+        // Row-major order indexing unless the storage order was changed
+        self.order.index(self.width, self.height, row, column)
+    }
+
+    // Converts a 1D index back into (row, column), the inverse of `get_index`
+    fn get_coords(&self, idx: usize) -> (u32, u32) {
+        self.order.coords(self.width, self.height, idx)
+    }
+
+    // Reads the cell at a 1D index back out of the bitset
+    fn cell(&self, idx: usize) -> Cell {
+        Cell::from(self.cells[idx])
+    }
+
+    // Smallest rectangle enclosing every live cell, as
+    // (min_row, min_col, max_row, max_col), or None for an empty board
+    fn live_extent(&self) -> Option<(u32, u32, u32, u32)> {
+        let mut bounds: Option<(u32, u32, u32, u32)> = None;
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.cells[self.get_index(row, col)] {
+                    bounds = Some(match bounds {
+                        None => (row, col, row, col),
+                        Some((r0, c0, r1, c1)) => {
+                            (r0.min(row), c0.min(col), r1.max(row), c1.max(col))
+                        }
+                    });
+                }
+            }
+        }
+        bounds
+    }
+
+    // Checks that `load_cells` can grow the grid to hold a `width` x `height`
+    // pattern, so importers can report a pattern that is too large instead
+    // of panicking in `resize`
+    fn check_pattern_fits(&self, width: u32, height: u32) -> Result<(), GameError> {
+        let (width, height) = (self.width.max(width), self.height.max(height));
+        validate_dimensions(width, height).map_err(|_| {
+            GameError::Parse(format!(
+                "pattern needs a {}x{} board, which has too many cells",
+                width, height
+            ))
+        })
+    }
+
+    // Replaces the board with an imported pattern placed at the top-left
+    // corner, growing the grid if the pattern doesn't fit
+    fn load_cells(&mut self, width: u32, height: u32, cells: &[(u32, u32)]) {
+        self.clear();
+        if width > self.width || height > self.height {
+            self.resize(self.width.max(width), self.height.max(height));
+        }
+        for &(row, col) in cells {
+            self.write_cell(row, col, Cell::Alive);
+        }
+    }
+
+    // Whether a cell at the given offset belongs to the neighborhood
+    fn in_neighborhood(&self, delta_row: i64, delta_col: i64) -> bool {
+        // Skip the center cell (the cell we're counting neighbors for)
+        if delta_row == 0 && delta_col == 0 {
+            return false;
+        }
+        // Von Neumann neighborhoods only reach `radius` steps orthogonally
+        match self.neighborhood {
+            Neighborhood::Moore => true,
+            Neighborhood::VonNeumann => delta_row.abs() + delta_col.abs() <= self.radius as i64,
+        }
     }
 
     // Counts living neighbors for Conway's Game of Life rules
     fn live_neighbor_count(&self, row: u32, column: u32) -> u8 {
+        let radius = self.radius;
+
+        // Interior cells can't reach an edge, so their neighbors are plain
+        // offsets from the cell's own index and the modulo can be skipped
+        if row >= radius
+            && column >= radius
+            && row + radius < self.height
+            && column + radius < self.width
+        {
+            let idx = self.get_index(row, column);
+            let (down, right) = self.order.strides(self.width, self.height);
+            if radius == 1 {
+                let orthogonal = self.cells[idx - down] as u8
+                    + self.cells[idx - right] as u8
+                    + self.cells[idx + right] as u8
+                    + self.cells[idx + down] as u8;
+                if self.neighborhood == Neighborhood::VonNeumann {
+                    return orthogonal;
+                }
+                return orthogonal
+                    + self.cells[idx - down - right] as u8
+                    + self.cells[idx - down + right] as u8
+                    + self.cells[idx + down - right] as u8
+                    + self.cells[idx + down + right] as u8;
+            }
+
+            let r = radius as i64;
+            let mut count = 0;
+            for delta_row in -r..=r {
+                for delta_col in -r..=r {
+                    if self.in_neighborhood(delta_row, delta_col) {
+                        let offset = delta_row * down as i64 + delta_col * right as i64;
+                        count += self.cells[(idx as i64 + offset) as usize] as u8;
+                    }
+                }
+            }
+            return count;
+        }
+
         let mut count = 0; // Initialize neighbor counter
 
-        // Iterate through 3x3 grid around target cell (using wrapping arithmetic)
-        for delta_row in [self.height - 1, 0, 1].iter().cloned() {
-            for delta_col in [self.width - 1, 0, 1].iter().cloned() {
-                // Skip the center cell (the cell we're counting neighbors for)
-                if delta_row == 0 && delta_col == 0 {
-                    continue;
+        // Border cells take the slower path that handles the edges, adding 1
+        // for each neighbor whose bit is set (alive) and 0 otherwise (dead)
+        self.for_each_neighbor(row, column, |idx| count += self.cells[idx] as u8);
+        count // Return total living neighbors
+    }
+
+    // Calls `visit` with the index of every neighbor of (row, col), wrapping
+    // around or stopping at the edges depending on the topology. Works for
+    // any cell, though interior cells can be handled faster with strides.
+    fn for_each_neighbor<F: FnMut(usize)>(&self, row: u32, col: u32, mut visit: F) {
+        let radius = self.radius;
+        match self.topology {
+            Topology::Toroidal => {
+                // Iterate through the square around the target cell (using wrapping arithmetic)
+                let row_deltas = wrap_deltas(self.height, radius);
+                let col_deltas = wrap_deltas(self.width, radius);
+                for &delta_row in &row_deltas {
+                    for &delta_col in &col_deltas {
+                        if !self.in_neighborhood(delta_row, delta_col) {
+                            continue;
+                        }
+
+                        // Calculate neighbor coordinates with wrapping (toroidal topology)
+                        let neighbor_row = (row as i64 + delta_row).rem_euclid(self.height as i64);
+                        let neighbor_col = (col as i64 + delta_col).rem_euclid(self.width as i64);
+
+                        // Hand over the 1D index for this neighbor
+                        visit(self.get_index(neighbor_row as u32, neighbor_col as u32));
+                    }
                 }
+            }
+            Topology::Bounded => {
+                // Iterate through the square around the target cell using signed offsets
+                let r = radius as i64;
+                for delta_row in -r..=r {
+                    for delta_col in -r..=r {
+                        if !self.in_neighborhood(delta_row, delta_col) {
+                            continue;
+                        }
+
+                        // Neighbors that fall off the grid are left out (they count as dead)
+                        let neighbor_row = row as i64 + delta_row;
+                        let neighbor_col = col as i64 + delta_col;
+                        if neighbor_row < 0
+                            || neighbor_col < 0
+                            || neighbor_row >= self.height as i64
+                            || neighbor_col >= self.width as i64
+                        {
+                            continue;
+                        }
 
-                // Calculate neighbor coordinates with wrapping (toroidal topology)
-                let neighbor_row = (row + delta_row) % self.height;
-                let neighbor_col = (column + delta_col) % self.width;
+                        visit(self.get_index(neighbor_row as u32, neighbor_col as u32));
+                    }
+                }
+            }
+        }
+    }
+
+    // Forgets per-cell tracking (changed cells, ages and dying cells) after
+    // the board has been replaced wholesale, e.g. by `clear` or `randomize`
+    fn reset_tracking(&mut self) {
+        let decay = vec![0; self.cells.len()];
+        self.reset_tracking_with_decay(decay);
+    }
 
-                // Get the 1D index for this neighbor
-                let idx = self.get_index(neighbor_row, neighbor_col);
+    // Like `reset_tracking`, but keeps the given dying and Wireworld states
+    // (laid out like `cells`) for a board that was moved, resized or
+    // restored rather than replaced
+    fn reset_tracking_with_decay(&mut self, decay: Vec<u8>) {
+        self.recount_population();
+        self.refresh_neighbor_counts();
+        self.wake_all();
+        self.changed.clear();
+        self.ages.clear();
+        self.ages.resize(self.cells.len(), 0);
+        self.decay = decay;
+        self.decay.resize(self.cells.len(), 0);
+        // Immortal cells only make sense at the dimensions they were set for
+        if self.immortal.len() != self.cells.len() {
+            self.immortal = FixedBitSet::new();
+        }
+    }
+
+    // Counts the live cells from scratch after `cells` was replaced directly
+    fn recount_population(&mut self) {
+        self.population = self.cells.count_ones(..) as u32;
+    }
+
+    // Brings a single cell to life or kills it, keeping the population in
+    // step. A cell that changes state starts over at age 0.
+    fn put_cell(&mut self, idx: usize, alive: bool) {
+        if self.cells[idx] == alive {
+            return;
+        }
+        self.cells.set(idx, alive);
+        self.ages[idx] = 0;
+        if alive {
+            self.population += 1;
+        } else {
+            self.population -= 1;
+        }
+        if !self.neighbor_counts.is_empty() {
+            let (row, col) = self.get_coords(idx);
+            self.shift_neighbor_counts(row, col, alive, &mut Vec::new());
+        }
+        self.mark_active(idx);
+    }
+
+    // Recounts every cell's neighbors if the cache is on, after the board or
+    // the shape of the neighborhood changed wholesale
+    fn refresh_neighbor_counts(&mut self) {
+        if self.neighbor_counts.is_empty() {
+            return;
+        }
+        self.neighbor_counts = (0..self.cells.len())
+            .map(|idx| {
+                let (row, col) = self.get_coords(idx);
+                self.live_neighbor_count(row, col)
+            })
+            .collect();
+    }
 
-                // Add 1 if alive (Cell::Alive = 1), 0 if dead (Cell::Dead = 0)
-                count += self.cells[idx] as u8;
+    // Updates the cached counts around (row, col) after it came alive or
+    // died. `neighbors` is scratch space, reused across calls by `tick`.
+    fn shift_neighbor_counts(
+        &mut self,
+        row: u32,
+        col: u32,
+        alive: bool,
+        neighbors: &mut Vec<usize>,
+    ) {
+        neighbors.clear();
+        self.for_each_neighbor(row, col, |idx| neighbors.push(idx));
+        for &idx in neighbors.iter() {
+            if alive {
+                self.neighbor_counts[idx] += 1;
+            } else {
+                self.neighbor_counts[idx] -= 1;
             }
         }
-        count // Return total living neighbors
+    }
+
+    // Computes the state a single cell will have in the next generation
+    fn next_cell(&self, row: u32, col: u32) -> Cell {
+        // Get the current state of the cell and count its live neighbors
+        let idx = self.get_index(row, col);
+        let cell = self.cell(idx);
+        let live_neighbors = if self.neighbor_counts.is_empty() {
+            self.live_neighbor_count(row, col)
+        } else {
+            self.neighbor_counts[idx]
+        };
+
+        // Conway's B3/S23 unless changed with `set_rule`
+        self.rule.next_cell(cell, live_neighbors)
+    }
+
+    // Computes every cell's next state into `next` on the rayon thread pool,
+    // in storage order. Each row (or column, when stored column-major) of the
+    // output is a disjoint chunk, and neighbor counting only reads
+    // `self.cells`, so the chunks can be filled independently.
+    #[cfg(feature = "parallel")]
+    fn next_cells_parallel(&self, next: &mut Vec<Cell>) {
+        use rayon::prelude::*;
+
+        let chunk_len = match self.order {
+            StorageOrder::RowMajor => self.width as usize,
+            StorageOrder::ColumnMajor => self.height as usize,
+        };
+        next.resize(self.cells.len(), Cell::Dead);
+        next.par_chunks_mut(chunk_len)
+            .enumerate()
+            .for_each(|(chunk, cells)| {
+                for (offset, cell) in cells.iter_mut().enumerate() {
+                    let (row, col) = self.get_coords(chunk * chunk_len + offset);
+                    *cell = self.next_cell(row, col);
+                }
+            });
     }
 
     // Advances the universe by one tick (generation) according to the Game of Life rules
     pub fn tick(&mut self) {
-        // Create a copy of the current cells to store the next state
-        let mut next = self.cells.clone();
+        // Borrow the scratch buffer to store the next state, reusing its
+        // allocation across ticks (it only needs reallocating after a resize)
+        let mut next = std::mem::take(&mut self.next);
+        if next.len() != self.cells.len() {
+            next = FixedBitSet::with_capacity(self.cells.len());
+        }
+        self.changed.clear();
+        self.record_history();
+
+        // A dead board stays dead unless the rule births cells with no
+        // neighbors (B0), so skip the scan entirely and just move time on
+        if self.is_dead_end() {
+            self.next = next;
+            self.generation += 1;
+            self.record_population();
+            return;
+        }
+
+        // Only revisit the cells near last tick's changes if that's enabled
+        let population = if self.can_tick_active() {
+            self.tick_active(&mut next)
+        } else {
+            self.tick_all(&mut next)
+        };
 
+        // Swap the next generation in and keep the old cells as the scratch buffer
+        std::mem::swap(&mut self.cells, &mut next);
+        self.next = next;
+        self.population = population;
+        if !self.neighbor_counts.is_empty() {
+            let mut neighbors = Vec::new();
+            for i in 0..self.changed.len() {
+                let (row, col) = (self.changed[i] / self.width, self.changed[i] % self.width);
+                let alive = self.cells[self.get_index(row, col)];
+                self.shift_neighbor_counts(row, col, alive, &mut neighbors);
+            }
+        }
+        if self.active_ticking {
+            self.update_active_set();
+        }
+        self.generation += 1;
+        self.record_population();
+        if self.auto_expand {
+            self.expand_to_fit();
+        }
+    }
+
+    // Computes the next state of every cell into `next`, recording which
+    // cells flip, and returns the new population
+    fn tick_all(&mut self, next: &mut FixedBitSet) -> u32 {
+        // With the `parallel` feature the next states are computed up front,
+        // one row per rayon task, and only the bookkeeping below runs serially.
+        // The buffer is borrowed for the tick like `next`.
+        #[cfg(feature = "parallel")]
+        let mut next_cells = std::mem::take(&mut self.next_cells);
+        #[cfg(feature = "parallel")]
+        self.next_cells_parallel(&mut next_cells);
+
+        let mut population = 0;
         // Iterate over each row in the universe
         for row in 0..self.height {
             // Iterate over each column in the universe
@@ -69,31 +703,379 @@ impl Universe {
                 // Calculate the 1D index for the current cell
                 let idx = self.get_index(row, col);
                 // Get the current state of the cell (Alive or Dead)
-                let cell = self.cells[idx];
-                // Count the number of live neighbors around the current cell
-                let live_neighbors = self.live_neighbor_count(row, col);
-
-                // Determine the next state of the cell based on the Game of Life rules
-                let next_cell = match (cell, live_neighbors) {
-                    // Rule 1: Any live cell with fewer than two live neighbours dies (underpopulation)
-                    (Cell::Alive, x) if x < 2 => Cell::Dead,
-                    // Rule 2: Any live cell with two or three live neighbours lives on
-                    (Cell::Alive, 2) | (Cell::Alive, 3) => Cell::Alive,
-                    // Rule 3: Any live cell with more than three live neighbours dies (overpopulation)
-                    (Cell::Alive, x) if x > 3 => Cell::Dead,
-                    // Rule 4: Any dead cell with exactly three live neighbours becomes alive (reproduction)
-                    (Cell::Dead, 3) => Cell::Alive,
-                    // All other cells remain in the same state
-                    (otherwise, _) => otherwise,
+                let cell = self.cell(idx);
+                // Determine the next state of the cell from the configured rule
+                #[cfg(feature = "parallel")]
+                let next_cell = next_cells[idx];
+                #[cfg(not(feature = "parallel"))]
+                let next_cell = self.next_cell(row, col);
+
+                let next_cell = self.settle_cell(idx, row, col, next_cell);
+
+                // Update the next state for this cell, remembering it if it flipped
+                next.set(idx, next_cell == Cell::Alive);
+                population += next_cell.is_alive() as u32;
+                if next_cell != cell {
+                    self.changed.push(row * self.width + col);
+                }
+
+                // Surviving cells age by one, newly born cells start at 1
+                self.ages[idx] = match next_cell {
+                    Cell::Alive => self.ages[idx] + 1,
+                    Cell::Dead => 0,
+                };
+            }
+        }
+        #[cfg(feature = "parallel")]
+        {
+            self.next_cells = next_cells;
+        }
+        population
+    }
+
+    // True when no cell can change on the next tick: nothing is alive, the
+    // rule doesn't birth cells from nothing, no cells are still decaying and
+    // no Wireworld wires need updating. With only 2 states nothing can be
+    // decaying, so the decay scan is skipped for plain Life.
+    fn is_dead_end(&self) -> bool {
+        self.automaton == Automaton::Life
+            && self.rule.next_cell(Cell::Dead, 0) == Cell::Dead
+            && self.population() == 0
+            && (self.states <= 2 || self.decay.iter().all(|&d| d == 0))
+    }
+
+    // Adjusts the rule's verdict `next_cell` for the cell at `idx`, updating
+    // its dying/Wireworld state. Immortal cells keep their state whatever the
+    // rule says. Under a Generations rule dying cells keep decaying and can't
+    // be reborn, and live cells that don't survive start dying.
+    fn settle_cell(&mut self, idx: usize, row: u32, col: u32, next_cell: Cell) -> Cell {
+        let cell = self.cell(idx);
+        if self.immortal.contains(idx) {
+            cell
+        } else if self.automaton == Automaton::Wireworld {
+            self.next_wireworld_cell(idx, row, col)
+        } else if self.decay[idx] > 0 {
+            self.decay[idx] -= 1;
+            Cell::Dead
+        } else {
+            if cell == Cell::Alive && next_cell == Cell::Dead {
+                self.decay[idx] = self.states - 2;
+            }
+            next_cell
+        }
+    }
+
+    // Applies the rules to the `w` x `h` rectangle at (row, col) only, leaving
+    // every other cell frozen, e.g. to preview how a freshly painted patch
+    // evolves. This isn't real Life: cells at the rectangle's edge read their
+    // outside neighbors from the frozen board. The rectangle is clipped at the
+    // edges like `fill_rect` and the generation counter doesn't advance.
+    pub fn tick_region(&mut self, row: u32, col: u32, w: u32, h: u32) {
+        self.record_history();
+        self.changed.clear();
+        let row_end = row.saturating_add(h).min(self.height);
+        let col_end = col.saturating_add(w).min(self.width);
+
+        // Work out every next state before writing any, so cells inside the
+        // region also see their neighbors' current states
+        let mut updates = Vec::new();
+        for r in row..row_end {
+            for c in col..col_end {
+                let idx = self.get_index(r, c);
+                let next_cell = self.next_cell(r, c);
+                updates.push((r, c, idx, self.settle_cell(idx, r, c, next_cell)));
+            }
+        }
+
+        for (r, c, idx, next_cell) in updates {
+            if next_cell != self.cell(idx) {
+                self.changed.push(r * self.width + c);
+            }
+            let age = self.ages[idx];
+            self.put_cell(idx, next_cell.is_alive());
+            // Surviving cells age by one, newly born cells start at 1
+            self.ages[idx] = match next_cell {
+                Cell::Alive => age + 1,
+                Cell::Dead => 0,
+            };
+        }
+    }
+
+    // Returns the next generation as a new universe without advancing this
+    // one. The copy keeps the same rule and topology, but has no undo history.
+    pub fn next_generation(&self) -> Universe {
+        let mut next = self.clone();
+        next.history.clear();
+        next.tick();
+        next
+    }
+
+    // Forks the board into an independent copy with the same cells, rule and
+    // settings, e.g. to compare rule variations side by side. `Clone` isn't
+    // visible from JS, so this exposes it.
+    pub fn duplicate(&self) -> Universe {
+        self.clone()
+    }
+
+    // Advances the universe by `n` generations in a single call, avoiding a
+    // JS -> wasm round trip per tick when fast-forwarding.
+    pub fn tick_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.tick();
+        }
+    }
+
+    // Runs one tick and returns how long it took in milliseconds, timed on
+    // the wasm side so the JS -> wasm call overhead isn't included. Uses
+    // `performance.now()` in the browser and `Instant` natively.
+    pub fn tick_timed(&mut self) -> f64 {
+        let stopwatch = utils::Stopwatch::start();
+        self.tick();
+        stopwatch.elapsed_ms()
+    }
+
+    // Ticks `frames` times and returns every resulting frame back to back,
+    // one byte (0 or 1) per cell in row-major order, e.g. for encoding an
+    // animation without crossing the JS -> wasm boundary per frame. The
+    // result holds `frames * width * height` bytes.
+    pub fn simulate(&mut self, frames: u32) -> Vec<u8> {
+        let mut buffer = Vec::with_capacity(frames as usize * self.cells.len());
+        for _ in 0..frames {
+            self.tick();
+            for row in 0..self.height {
+                for col in 0..self.width {
+                    buffer.push(self.cells[self.get_index(row, col)] as u8);
+                }
+            }
+        }
+        buffer
+    }
+
+    // Flips a single cell between Alive and Dead, e.g. when the user clicks it.
+    // Out-of-range coordinates wrap around the edges the same way neighbor
+    // counting does, so this never panics on `row >= height`.
+    pub fn toggle_cell(&mut self, row: u32, col: u32) {
+        self.record_history();
+        let idx = self.get_index(row % self.height, col % self.width);
+        self.put_cell(idx, !self.cells[idx]);
+        self.decay[idx] = 0;
+    }
+
+    // Number of live neighbors a cell has under the current topology,
+    // neighborhood and radius, e.g. to explain why it will live or die next
+    // tick. Coordinates wrap like `get_cell`.
+    pub fn neighbor_count(&self, row: u32, col: u32) -> u8 {
+        self.live_neighbor_count(row % self.height, col % self.width)
+    }
+
+    // Which of a cell's 8 surrounding cells are alive, one bit per direction
+    // in reading order: bit 0 NW, 1 N, 2 NE, 3 W, 4 E, 5 SW, 6 S, 7 SE. Edges
+    // wrap or count as dead according to the topology; the neighborhood and
+    // radius settings don't apply. Coordinates wrap like `get_cell`.
+    pub fn neighbor_mask(&self, row: u32, col: u32) -> u8 {
+        let (row, col) = (row % self.height, col % self.width);
+        let (width, height) = (self.width as i64, self.height as i64);
+        let mut mask = 0;
+        let mut bit = 0;
+        for delta_row in -1i64..=1 {
+            for delta_col in -1i64..=1 {
+                if delta_row == 0 && delta_col == 0 {
+                    continue;
+                }
+                let neighbor_row = row as i64 + delta_row;
+                let neighbor_col = col as i64 + delta_col;
+                let alive = match self.topology {
+                    Topology::Toroidal => {
+                        self.cells[self.get_index(
+                            neighbor_row.rem_euclid(height) as u32,
+                            neighbor_col.rem_euclid(width) as u32,
+                        )]
+                    }
+                    Topology::Bounded => {
+                        (0..height).contains(&neighbor_row)
+                            && (0..width).contains(&neighbor_col)
+                            && self.cells[self.get_index(neighbor_row as u32, neighbor_col as u32)]
+                    }
                 };
+                mask |= (alive as u8) << bit;
+                bit += 1;
+            }
+        }
+        mask
+    }
+
+    // Marks a cell as immortal (or mortal again). Immortal cells still count
+    // as neighbors but `tick` never changes them, so a live one acts as a
+    // permanent wall and a dead one as a hole nothing can grow into. The
+    // marks are dropped when the board's dimensions change. Coordinates wrap
+    // like `toggle_cell`.
+    pub fn set_immortal(&mut self, row: u32, col: u32, on: bool) {
+        let idx = self.get_index(row % self.height, col % self.width);
+        if self.immortal.len() != self.cells.len() {
+            self.immortal = FixedBitSet::with_capacity(self.cells.len());
+        }
+        self.immortal.set(idx, on);
+        self.mark_active(idx);
+    }
+
+    pub fn is_immortal(&self, row: u32, col: u32) -> bool {
+        self.immortal
+            .contains(self.get_index(row % self.height, col % self.width))
+    }
+
+    // Reads a single cell's state, e.g. for a hover tooltip. Coordinates wrap
+    // like `toggle_cell`.
+    pub fn get_cell(&self, row: u32, col: u32) -> Cell {
+        self.cell(self.get_index(row % self.height, col % self.width))
+    }
+
+    // Sets a single cell to the given state regardless of its current one,
+    // e.g. when replaying a recorded pattern. Unlike `toggle_cell` the
+    // coordinates don't wrap: anything off the board is an `OutOfBounds` error.
+    pub fn set_cell(&mut self, row: u32, col: u32, cell: Cell) -> Result<(), GameError> {
+        if row >= self.height || col >= self.width {
+            return Err(GameError::OutOfBounds {
+                row,
+                col,
+                width: self.width,
+                height: self.height,
+            });
+        }
+        self.record_history();
+        self.write_cell(row, col, cell);
+        Ok(())
+    }
+
+    // Sets many cells alive in one call. `coords` holds flattened (row, col)
+    // pairs, e.g. `[1, 2, 3, 4]` for (1, 2) and (3, 4). Coordinates wrap like
+    // `toggle_cell`, and a trailing unpaired value is ignored.
+    pub fn set_cells(&mut self, coords: &[u32]) {
+        self.record_history();
+        for pair in coords.chunks_exact(2) {
+            self.write_cell(pair[0], pair[1], Cell::Alive);
+        }
+    }
+
+    // Sets every cell within Euclidean distance `radius` of the center to
+    // `state`, like a round brush. On a toroidal board the disc wraps around
+    // the edges; on a bounded one the part past an edge is clipped.
+    pub fn paint_disc(&mut self, center_row: u32, center_col: u32, radius: u32, state: Cell) {
+        self.record_history();
+        let r = radius as i64;
+        let (width, height) = (self.width as i64, self.height as i64);
+        for delta_row in -r..=r {
+            for delta_col in -r..=r {
+                if delta_row * delta_row + delta_col * delta_col > r * r {
+                    continue;
+                }
+                let row = center_row as i64 + delta_row;
+                let col = center_col as i64 + delta_col;
+                match self.topology {
+                    Topology::Toroidal => self.write_cell(
+                        row.rem_euclid(height) as u32,
+                        col.rem_euclid(width) as u32,
+                        state,
+                    ),
+                    Topology::Bounded => {
+                        if (0..height).contains(&row) && (0..width).contains(&col) {
+                            self.write_cell(row as u32, col as u32, state);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Sets every cell in the `w` x `h` rectangle whose top-left corner is at
+    // (row, col) to `state`. Unlike single-cell edits the rectangle doesn't
+    // wrap: anything past the bottom or right edge is clipped.
+    pub fn fill_rect(&mut self, row: u32, col: u32, w: u32, h: u32, state: Cell) {
+        self.record_history();
+        let row_end = row.saturating_add(h).min(self.height);
+        let col_end = col.saturating_add(w).min(self.width);
+        for r in row..row_end {
+            for c in col..col_end {
+                self.write_cell(r, c, state);
+            }
+        }
+    }
+
+    // Writes one cell without recording undo history, for bulk edits that
+    // record a single snapshot up front. Coordinates wrap around the edges
+    // like `toggle_cell`.
+    fn write_cell(&mut self, row: u32, col: u32, cell: Cell) {
+        let idx = self.get_index(row % self.height, col % self.width);
+        self.decay[idx] = 0;
+        self.put_cell(idx, cell.is_alive());
+    }
+
+    // Fills the board randomly, making each cell alive with probability
+    // `density` (0.0 to 1.0). The same seed always produces the same board.
+    pub fn randomize(&mut self, seed: u64, density: f64) {
+        let mut rng = utils::Rng::new(seed);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                self.cells.set(idx, rng.next_f64() < density);
+            }
+        }
+        self.reset_tracking();
+        self.generation = 0;
+    }
 
-                // Update the next state for this cell
-                next[idx] = next_cell;
+    // Fills only the `w` x `h` rectangle at (row, col) with random cells,
+    // like `randomize` but leaving everything outside it untouched. The
+    // rectangle is clipped at the edges like `fill_rect`, and the same seed
+    // always produces the same noise.
+    pub fn randomize_rect(&mut self, row: u32, col: u32, w: u32, h: u32, seed: u64, density: f64) {
+        self.record_history();
+        let mut rng = utils::Rng::new(seed);
+        let row_end = row.saturating_add(h).min(self.height);
+        let col_end = col.saturating_add(w).min(self.width);
+        for r in row..row_end {
+            for c in col..col_end {
+                let cell = Cell::from(rng.next_f64() < density);
+                self.write_cell(r, c, cell);
             }
         }
+    }
+
+    // Restores the initial `new()` seeding for the current dimensions and
+    // resets the generation counter. The rule and topology are kept.
+    pub fn reset(&mut self) {
+        let seed = seed_cells(self.width, self.height);
+        self.cells.clear();
+        for (i, cell) in seed.into_iter().enumerate() {
+            let (row, col) = (i as u32 / self.width, i as u32 % self.width);
+            let idx = self.get_index(row, col);
+            self.cells.set(idx, cell.is_alive());
+        }
+        self.reset_tracking();
+        self.generation = 0;
+        self.population_history.clear();
+    }
 
-        // Replace the current cells with the next generation
-        self.cells = next;
+    // Stamps the canonical glider with its 3x3 bounding box's top-left corner
+    // at (row, col), wrapping around the edges.
+    pub fn insert_glider(&mut self, row: u32, col: u32) {
+        self.insert_pattern(Pattern::Glider, row, col);
+    }
+
+    // Stamps the Gosper glider gun (36x9 cells) with its top-left corner at
+    // (row, col), wrapping around the edges. It fires a new glider every 30
+    // ticks, so give it a board of at least 40x40 to watch it run before the
+    // gliders wrap around into the gun.
+    pub fn insert_glider_gun(&mut self, row: u32, col: u32) {
+        self.insert_pattern(Pattern::GosperGliderGun, row, col);
+    }
+
+    // Resets every cell to Dead while keeping the current dimensions.
+    pub fn clear(&mut self) {
+        self.record_history();
+        self.cells.clear();
+        self.reset_tracking();
+        self.generation = 0;
+        self.population_history.clear();
     }
 }
 
@@ -106,20 +1088,38 @@ impl fmt::Display for Universe {
     // The fmt method defines how the Universe should be formatted.
     // It writes each row of cells as a line of symbols: '◻' for dead, '◼' for alive.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // `as_slice()` returns a slice reference to the underlying vector of cells,
-        // allowing us to work with the data as a contiguous sequence.
-        // `chunks(self.width as usize)` then splits this slice into sub-slices (rows),
-        // each of length equal to the universe's width.
-        for line in self.cells.as_slice().chunks(self.width as usize) {
+        self.write_grid(f, '◼', '◻')
+    }
+}
+
+impl Universe {
+    // Writes each row of cells as a line of symbols, shared by `Display` and
+    // the configurable renderers.
+    fn write_grid<W: fmt::Write>(&self, out: &mut W, alive: char, dead: char) -> fmt::Result {
+        self.write_region(out, 0..self.height, 0..self.width, alive, dead)
+    }
+
+    // Writes the cells in the given rows and columns the same way
+    fn write_region<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        rows: std::ops::Range<u32>,
+        cols: std::ops::Range<u32>,
+        alive: char,
+        dead: char,
+    ) -> fmt::Result {
+        // Walk the grid row by row, reading each cell's bit from the bitset.
+        for row in rows {
             // Iterate over each cell in the current row.
-            for &cell in line {
+            for col in cols.clone() {
+                let idx = self.get_index(row, col);
                 // Choose a symbol based on whether the cell is dead or alive.
-                let symbol = if cell == Cell::Dead { '◻' } else { '◼' };
-                // Write the symbol to the formatter.
-                write!(f, "{}", symbol)?;
+                let symbol = if self.cells[idx] { alive } else { dead };
+                // Write the symbol to the output.
+                write!(out, "{}", symbol)?;
             }
             // Write a newline character after each row.
-            write!(f, "\n")?;
+            writeln!(out)?;
         }
 
         // Indicate that formatting was successful.
@@ -133,35 +1133,218 @@ impl Universe {
 
     // Public function to create a new Universe instance
     pub fn new() -> Universe {
-        let width = 64; // Set the width of the universe grid to 64 cells
-        let height = 64; // Set the height of the universe grid to 64 cells
-
-        // Create a vector of cells for the universe
-        // For each cell index from 0 to width*height - 1:
-        let cells = (0..width * height)
-            .map(|i| {
-                // If the index is divisible by 2 or 7, make the cell alive
-                if i % 2 == 0 || i % 7 == 0 {
-                    Cell::Alive
-                } else {
-                    // Otherwise, make the cell dead
-                    Cell::Dead
-                }
-            })
-            .collect(); // Collect the results into a vector
+        // Default to a 64x64 grid
+        Universe::new_with_size(64, 64)
+    }
+
+    // Creates a universe with custom dimensions, seeded with the same pattern
+    // as `new()`: every cell whose index is divisible by 2 or 7 starts alive.
+    // Panics if either dimension is zero, since an empty grid has no cells to
+    // wrap neighbors around.
+    pub fn new_with_size(width: u32, height: u32) -> Universe {
+        check_dimensions(width, height);
+
+        // Create a bitset with one bit per cell, seeded with the default pattern
+        let size = (width * height) as usize;
+        let mut cells = FixedBitSet::with_capacity(size);
+        for (i, cell) in seed_cells(width, height).into_iter().enumerate() {
+            cells.set(i, cell.is_alive());
+        }
 
         // Return a new Universe struct with the specified width, height, and cells
         Universe {
             width,
             height,
+            population: cells.count_ones(..) as u32,
             cells,
+            next: FixedBitSet::new(),
+            #[cfg(feature = "parallel")]
+            next_cells: Vec::new(),
+            changed: Vec::new(),
+            ages: vec![0; size],
+            generation: 0,
+            topology: Topology::Toroidal,
+            neighborhood: Neighborhood::Moore,
+            radius: 1,
+            neighbor_counts: Vec::new(),
+            active_ticking: false,
+            active: FixedBitSet::new(),
+            all_active: true,
+            auto_expand: false,
+            immortal: FixedBitSet::new(),
+            rule: Rule::default(),
+            order: StorageOrder::RowMajor,
+            automaton: Automaton::Life,
+            states: 2,
+            decay: vec![0; size],
+            history: History::new(),
+            history_depth: history::DEFAULT_HISTORY_DEPTH,
+            checkpoints: Vec::new(),
+            population_history: Vec::new(),
+            population_history_limit: 0,
         }
     }
 
+    // Creates an all-dead universe, e.g. as a blank canvas for hand-drawn
+    // boards. Panics if either dimension is zero.
+    pub fn empty(width: u32, height: u32) -> Universe {
+        let mut universe = Universe::new_with_size(width, height);
+        universe.cells.clear();
+        universe.population = 0;
+        universe
+    }
+
+    // Creates a universe from one byte per cell in row-major order, where any
+    // nonzero byte is a live cell. Returns an error if `cells` doesn't hold
+    // exactly `width * height` bytes or the dimensions are invalid (zero, or
+    // too many cells).
+    pub fn from_cells(width: u32, height: u32, cells: &[u8]) -> Result<Universe, GameError> {
+        validate_dimensions(width, height)?;
+        let size = width as u64 * height as u64;
+        if cells.len() as u64 != size {
+            return Err(GameError::DimensionMismatch {
+                expected: size as usize,
+                actual: cells.len(),
+            });
+        }
+
+        let mut universe = Universe::empty(width, height);
+        for (i, &cell) in cells.iter().enumerate() {
+            universe.cells.set(i, cell != 0);
+        }
+        universe.recount_population();
+        Ok(universe)
+    }
+
     // Public function to render the universe as a string
     pub fn render(&self) -> String {
         self.to_string() // Use the Display trait to convert the universe to a string
     }
+
+    // Renders the universe like `render` but with the caller's glyphs, e.g.
+    // `render_with('#', '.')` for terminals without the unicode squares.
+    pub fn render_with(&self, alive_char: char, dead_char: char) -> String {
+        let mut out = String::new();
+        self.write_grid(&mut out, alive_char, dead_char)
+            .expect("writing to a String never fails");
+        out
+    }
+
+    // Renders only the `w` x `h` rectangle at (row, col) like `render`, e.g.
+    // to log the neighborhood of one pattern on a large board. The rectangle
+    // is clipped at the edges like `fill_rect`.
+    pub fn render_region(&self, row: u32, col: u32, w: u32, h: u32) -> String {
+        let rows = row.min(self.height)..row.saturating_add(h).min(self.height);
+        let cols = col.min(self.width)..col.saturating_add(w).min(self.width);
+        let mut out = String::new();
+        self.write_region(&mut out, rows, cols, '◼', '◻')
+            .expect("writing to a String never fails");
+        out
+    }
+}
+
+impl Default for Universe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Two universes are equal when they have the same dimensions and cells.
+// Scratch buffers and per-tick tracking (generation, changed cells, ages) are
+// ignored, so a board that revisits an earlier state compares equal to it.
+impl PartialEq for Universe {
+    fn eq(&self, other: &Universe) -> bool {
+        if self.width != other.width || self.height != other.height {
+            return false;
+        }
+        if self.order == other.order {
+            return self.cells == other.cells;
+        }
+        (0..self.height).all(|row| {
+            (0..self.width).all(|col| {
+                self.cells[self.get_index(row, col)] == other.cells[other.get_index(row, col)]
+            })
+        })
+    }
+}
+
+impl Eq for Universe {}
+
+// Rust-only helpers that can't cross the wasm boundary
+impl Universe {
+    // Iterates over the (row, col) of every live cell in storage order
+    // (row-major unless `set_storage_order` changed it)
+    pub fn live_cells_iter(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.cells.ones().map(move |idx| self.get_coords(idx))
+    }
+
+    // Writes the next generation into `out` in row-major order without
+    // advancing the universe, resizing it to `width * height` if needed. Lets
+    // an embedder ping-pong between buffers it owns instead of allocating a
+    // new board each step. Immortal cells, dying cells and Wireworld are all
+    // taken into account, the same as `tick`.
+    pub fn tick_into(&self, out: &mut Vec<Cell>) {
+        out.resize((self.width * self.height) as usize, Cell::Dead);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                out[(row * self.width + col) as usize] = if self.immortal.contains(idx) {
+                    self.cell(idx)
+                } else if self.automaton == Automaton::Wireworld {
+                    self.wireworld_cell(idx, row, col)
+                } else if self.decay[idx] > 0 {
+                    Cell::Dead
+                } else {
+                    self.next_cell(row, col)
+                };
+            }
+        }
+    }
+
+    // Ticks `period` times and returns how far the centroid of the live cells
+    // moved as (rows, cols), e.g. (1.0, 1.0) for a glider heading down-right
+    // over its period of 4. On a toroidal board a jump of more than half the
+    // board is taken to be a wrap around the edge the other way, so keep
+    // `period` short enough that the pattern moves less than that, and start
+    // and stop while it isn't straddling an edge (see `centroid`). Returns
+    // (0.0, 0.0) if the board is or becomes empty.
+    pub fn drift_over(&mut self, period: u32) -> (f64, f64) {
+        let start = self.centroid();
+        self.tick_n(period);
+        let (start, end) = match (start, self.centroid()) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return (0.0, 0.0),
+        };
+
+        let unwrap = |delta: f64, size: u32| {
+            let size = size as f64;
+            if self.topology != Topology::Toroidal {
+                delta
+            } else if delta > size / 2.0 {
+                delta - size
+            } else if delta < -size / 2.0 {
+                delta + size
+            } else {
+                delta
+            }
+        };
+        (
+            unwrap(end.row - start.row, self.height),
+            unwrap(end.col - start.col, self.width),
+        )
+    }
+
+    // Ticks until `should_stop` returns true, calling it with the universe
+    // after every tick, e.g. to record frames or watch for a condition
+    // without copying the board out. Loops forever if it never returns true.
+    pub fn run_until<F: FnMut(&Universe) -> bool>(&mut self, mut should_stop: F) {
+        loop {
+            self.tick();
+            if should_stop(self) {
+                break;
+            }
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -172,7 +1355,345 @@ impl Universe {
     pub fn height(&self) -> u32 {
         self.height
     }
-    pub fn cells(&self) -> *const Cell {
-        self.cells.as_ptr()
+
+    // Number of ticks since the universe was created or last cleared.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    // Switches between wrapping edges and a fixed dead border.
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+        self.refresh_neighbor_counts();
+        self.wake_all();
+    }
+
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    // Switches between counting all 8 surrounding cells (Moore, the default)
+    // and only the 4 orthogonal ones (von Neumann).
+    pub fn set_neighborhood(&mut self, neighborhood: Neighborhood) {
+        self.neighborhood = neighborhood;
+        self.refresh_neighbor_counts();
+        self.wake_all();
+    }
+
+    pub fn storage_order(&self) -> StorageOrder {
+        self.order
+    }
+
+    // Switches how cells are laid out in memory, e.g. to column-major for
+    // rules that walk the board column by column. The board itself doesn't
+    // change, but `cells()` and `ages()` then follow the new layout. Ages are
+    // reset like after a `resize`.
+    pub fn set_storage_order(&mut self, order: StorageOrder) {
+        if order == self.order {
+            return;
+        }
+        let (width, height) = (self.width, self.height);
+        self.cells = reorder(&self.cells, width, height, self.order, order);
+        if self.immortal.len() == self.cells.len() {
+            self.immortal = reorder(&self.immortal, width, height, self.order, order);
+        }
+        let mut decay = vec![0; self.decay.len()];
+        for (idx, &state) in self.decay.iter().enumerate() {
+            let (row, col) = self.get_coords(idx);
+            decay[order.index(width, height, row, col)] = state;
+        }
+        self.order = order;
+        self.reset_tracking_with_decay(decay);
+    }
+
+    pub fn neighbor_radius(&self) -> u32 {
+        self.radius
+    }
+
+    // Widens the neighborhood to every cell within `r` steps (1 is the usual
+    // 3x3 block, 2 a 5x5 block, ...). Clamped to 1..=5; pair it with
+    // `set_rule` since counts above 8 need rules of their own.
+    pub fn set_neighbor_radius(&mut self, r: u32) {
+        self.radius = r.clamp(1, MAX_RADIUS);
+        self.refresh_neighbor_counts();
+        self.wake_all();
+    }
+
+    pub fn neighbor_cache(&self) -> bool {
+        !self.neighbor_counts.is_empty()
+    }
+
+    // Keeps every cell's live-neighbor count in a buffer (one byte per cell)
+    // that is patched whenever a cell flips, so `tick` reads the counts
+    // instead of summing neighbors. Pays off on dense, busy boards; on quiet
+    // ones the bookkeeping can cost more than it saves. Off by default.
+    pub fn set_neighbor_cache(&mut self, enabled: bool) {
+        if enabled == self.neighbor_cache() {
+            return;
+        }
+        if enabled {
+            self.neighbor_counts = vec![0; self.cells.len()];
+            self.refresh_neighbor_counts();
+        } else {
+            self.neighbor_counts = Vec::new();
+        }
+    }
+
+    // Replaces the birth/survival rule, e.g. `set_rule(&[3, 6], &[2, 3])` for
+    // HighLife (B36/S23). Each slice lists the live-neighbor counts that cause
+    // a dead cell to be born or a live cell to survive.
+    pub fn set_rule(&mut self, birth: &[u8], survival: &[u8]) {
+        self.rule = Rule::from_counts(birth, survival);
+        self.wake_all();
+    }
+
+    // Replaces the rule with a well-known one, e.g. `RulePreset::HighLife`
+    pub fn set_rule_preset(&mut self, preset: RulePreset) {
+        self.rule = preset.rule();
+        self.wake_all();
+    }
+
+    // The current rule in `Bxxx/Sxxx` notation, e.g. "B3/S23"
+    pub fn rule_string(&self) -> String {
+        self.rule.to_string()
+    }
+
+    // Replaces the rule from a string in `Bxxx/Sxxx` notation such as "B36/S23".
+    // Malformed strings leave the current rule untouched and return an error.
+    pub fn set_rule_string(&mut self, rule: &str) -> Result<(), GameError> {
+        self.rule = rule.parse().map_err(GameError::Parse)?;
+        self.wake_all();
+        Ok(())
+    }
+
+    // Sets the width of the universe. Resets all cells to the dead state.
+    pub fn set_width(&mut self, width: u32) {
+        check_dimensions(width, self.height);
+        self.width = width;
+        self.cells = FixedBitSet::with_capacity((width * self.height) as usize);
+        self.reset_tracking();
+        self.generation = 0;
+    }
+
+    // Sets the height of the universe. Resets all cells to the dead state.
+    pub fn set_height(&mut self, height: u32) {
+        check_dimensions(self.width, height);
+        self.height = height;
+        self.cells = FixedBitSet::with_capacity((self.width * height) as usize);
+        self.reset_tracking();
+        self.generation = 0;
+    }
+
+    // Grows or shrinks the grid while keeping the existing pattern. Cells that
+    // still fall inside the new bounds are copied over, new cells start dead and
+    // cells outside a shrunk grid are dropped.
+    pub fn resize(&mut self, new_width: u32, new_height: u32) {
+        check_dimensions(new_width, new_height);
+
+        let mut cells = FixedBitSet::with_capacity((new_width * new_height) as usize);
+        let mut decay = vec![0; cells.len()];
+        for row in 0..self.height.min(new_height) {
+            for col in 0..self.width.min(new_width) {
+                let idx = self.get_index(row, col);
+                let new_idx = self.order.index(new_width, new_height, row, col);
+                cells.set(new_idx, self.cells[idx]);
+                decay[new_idx] = self.decay[idx];
+            }
+        }
+
+        self.width = new_width;
+        self.height = new_height;
+        self.cells = cells;
+        self.reset_tracking_with_decay(decay);
+    }
+    // Pointer to the bit-packed cells for reading straight out of wasm memory.
+    // Cell `i` is bit `i % 32` of the little-endian u32 word `i / 32`, which is
+    // the same as bit `i % 8` of byte `i / 8`. `i` is `row * width + col`
+    // unless `set_storage_order` switched the board to column-major.
+    pub fn cells(&self) -> *const u32 {
+        self.cells.as_slice().as_ptr() as *const u32
+    }
+
+    // Number of cells behind the `cells()` pointer (one bit each), so JS can
+    // size its view without recomputing `width * height`. The pointer covers
+    // `Math.ceil(cells_len() / 8)` bytes.
+    pub fn cells_len(&self) -> usize {
+        self.cells.len()
+    }
+
+    // Indices (row * width + col) of the cells whose state differs between the
+    // previous and current generation, so renderers can redraw only those.
+    pub fn changed_cells(&self) -> Vec<u32> {
+        self.changed.clone()
+    }
+
+    // Indices (row * width + col) of the cells that the next tick will flip,
+    // e.g. to preview births and deaths, without advancing the universe.
+    // `changed_cells` reports the previous tick's changes instead.
+    pub fn pending_changes(&self) -> Vec<u32> {
+        let mut next = Vec::new();
+        self.tick_into(&mut next);
+        next.iter()
+            .enumerate()
+            .filter(|&(i, &cell)| {
+                let (row, col) = (i as u32 / self.width, i as u32 % self.width);
+                cell != self.cell(self.get_index(row, col))
+            })
+            .map(|(i, _)| i as u32)
+            .collect()
+    }
+
+    // Smallest rectangle enclosing every live cell, e.g. for centering the
+    // camera or exporting a tight pattern. Returns None for an empty board.
+    pub fn live_bounds(&self) -> Option<Bounds> {
+        self.live_extent()
+            .map(|(min_row, min_col, max_row, max_col)| Bounds {
+                min_row,
+                min_col,
+                max_row,
+                max_col,
+            })
+    }
+
+    // Center of mass of the live cells, e.g. for a camera following a
+    // glider. Returns None for an empty board. The average is taken in raw
+    // grid coordinates, ignoring the toroidal wrap, so a pattern straddling
+    // the seam reports a point between its two halves.
+    pub fn centroid(&self) -> Option<Centroid> {
+        let (mut rows, mut cols, mut count) = (0u64, 0u64, 0u64);
+        for (row, col) in self.live_cells_iter() {
+            rows += row as u64;
+            cols += col as u64;
+            count += 1;
+        }
+        if count == 0 {
+            return None;
+        }
+        Some(Centroid {
+            row: rows as f64 / count as f64,
+            col: cols as f64 / count as f64,
+        })
+    }
+
+    // Index (row * width + col) of the live cell closest to (row, col) by
+    // Chebyshev distance, i.e. the smallest square around the point that
+    // contains one, e.g. to jump the view to the nearest activity. Searches
+    // outward ring by ring, so nearby cells are found quickly on a big board.
+    // Distances wrap around the edges on a toroidal board. Ties go to the
+    // cell that comes first row by row within the ring. Returns None for an
+    // empty board. Coordinates wrap like `get_cell`.
+    pub fn nearest_live(&self, row: u32, col: u32) -> Option<u32> {
+        if self.population == 0 {
+            return None;
+        }
+        let (row, col) = ((row % self.height) as i64, (col % self.width) as i64);
+        let (width, height) = (self.width as i64, self.height as i64);
+        for distance in 0..=width.max(height) {
+            for delta_row in -distance..=distance {
+                // Whole top and bottom edges of the ring, only the two ends
+                // of the rows in between
+                let step = if delta_row.abs() == distance {
+                    1
+                } else {
+                    (2 * distance).max(1)
+                };
+                for delta_col in (-distance..=distance).step_by(step as usize) {
+                    let (r, c) = (row + delta_row, col + delta_col);
+                    let (r, c) = match self.topology {
+                        Topology::Toroidal => (r.rem_euclid(height), c.rem_euclid(width)),
+                        Topology::Bounded => {
+                            if !(0..height).contains(&r) || !(0..width).contains(&c) {
+                                continue;
+                            }
+                            (r, c)
+                        }
+                    };
+                    if self.cells[self.get_index(r as u32, c as u32)] {
+                        return Some((r * width + c) as u32);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // Pointer to the per-cell ages (one u32 per cell, in storage order), for
+    // rendering heatmaps straight out of wasm memory. An age counts the ticks a
+    // cell has stayed alive: newly born cells are 1, dead cells are 0.
+    pub fn ages(&self) -> *const u32 {
+        self.ages.as_ptr()
+    }
+
+    // Number of entries behind the `ages()` pointer
+    pub fn ages_len(&self) -> usize {
+        self.ages.len()
+    }
+
+    // Age of a single cell. Coordinates wrap like `get_cell`.
+    pub fn age(&self, row: u32, col: u32) -> u32 {
+        self.ages[self.get_index(row % self.height, col % self.width)]
+    }
+
+    // Coordinates of every live cell as flattened (row, col) pairs in
+    // row-major order, e.g. `[r0, c0, r1, c1, ...]`.
+    pub fn live_cells(&self) -> Vec<u32> {
+        let mut cells: Vec<(u32, u32)> = self.live_cells_iter().collect();
+        if self.order == StorageOrder::ColumnMajor {
+            cells.sort_unstable();
+        }
+        cells
+            .into_iter()
+            .flat_map(|(row, col)| [row, col])
+            .collect()
+    }
+
+    // Number of alive cells, e.g. for a live-cell readout or to detect extinction.
+    // Kept up to date as cells change, so it is cheap to call every frame.
+    pub fn population(&self) -> u32 {
+        self.population
+    }
+
+    // Whether every cell is dead, e.g. to disable a "clear" button
+    pub fn is_empty(&self) -> bool {
+        self.population == 0
+    }
+
+    // Whether every cell is alive
+    pub fn is_full(&self) -> bool {
+        self.population as usize == self.cells.len()
+    }
+
+    // Number of alive cells in the `w` x `h` rectangle at (row, col), e.g.
+    // for heatmap tiles, without copying the cells out to JS. The rectangle
+    // is clipped at the edges like `fill_rect`.
+    pub fn population_in_rect(&self, row: u32, col: u32, w: u32, h: u32) -> u32 {
+        let row_end = row.saturating_add(h).min(self.height);
+        let col_end = col.saturating_add(w).min(self.width);
+        if row >= row_end || col >= col_end {
+            return 0;
+        }
+        // Each row (or column, in column-major order) of the rectangle is a
+        // contiguous run of bits that can be counted a word at a time
+        let count = match self.order {
+            StorageOrder::RowMajor => (row..row_end)
+                .map(|r| {
+                    let start = self.get_index(r, col);
+                    self.cells
+                        .count_ones(start..start + (col_end - col) as usize)
+                })
+                .sum::<usize>(),
+            StorageOrder::ColumnMajor => (col..col_end)
+                .map(|c| {
+                    let start = self.get_index(row, c);
+                    self.cells
+                        .count_ones(start..start + (row_end - row) as usize)
+                })
+                .sum::<usize>(),
+        };
+        count as u32
     }
 }
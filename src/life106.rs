@@ -0,0 +1,63 @@
+// Life 1.06 pattern import
+//
+// After a `#Life 1.06` header each line holds the `x y` coordinates of one
+// live cell, where x is the column and y the row. Coordinates may be
+// negative since they are relative to the pattern's own origin.
+
+use crate::{GameError, Universe};
+use std::convert::TryFrom;
+use wasm_bindgen::prelude::*;
+
+// Number of cells from `min` to `max` inclusive, if that fits in a u32
+fn span(min: i64, max: i64) -> Option<u32> {
+    let span = (max as i128 - min as i128) + 1;
+    u32::try_from(span).ok()
+}
+
+#[wasm_bindgen]
+impl Universe {
+    // Replaces the board with a Life 1.06 pattern, shifted so its smallest
+    // row and column land on the board's top-left corner. The board is
+    // cleared first and grown if the pattern doesn't fit. Blank lines and
+    // lines starting with `#` are skipped, as is any line that isn't a pair
+    // of integers. Returns an error, leaving the board untouched, if the
+    // pattern spans too many cells to fit on a board.
+    pub fn load_life106(&mut self, text: &str) -> Result<(), GameError> {
+        let points: Vec<(i64, i64)> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let x = fields.next()?.parse().ok()?;
+                let y = fields.next()?.parse().ok()?;
+                match fields.next() {
+                    Some(_) => None,
+                    None => Some((y, x)),
+                }
+            })
+            .collect();
+
+        let min_row = points.iter().map(|&(row, _)| row).min().unwrap_or(0);
+        let min_col = points.iter().map(|&(_, col)| col).min().unwrap_or(0);
+        let max_row = points.iter().map(|&(row, _)| row).max().unwrap_or(-1);
+        let max_col = points.iter().map(|&(_, col)| col).max().unwrap_or(-1);
+        let (height, width) = match (span(min_row, max_row), span(min_col, max_col)) {
+            (Some(height), Some(width)) => (height, width),
+            _ => {
+                return Err(GameError::Parse(
+                    "Life 1.06 pattern is too large".to_string(),
+                ))
+            }
+        };
+        self.check_pattern_fits(width, height)?;
+
+        // Both spans fit in a u32, so every offset from the minimum does too
+        let cells: Vec<(u32, u32)> = points
+            .iter()
+            .map(|&(row, col)| ((row - min_row) as u32, (col - min_col) as u32))
+            .collect();
+        self.load_cells(width, height, &cells);
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+// Built-in patterns that can be stamped onto a universe
+
+use crate::{Cell, Universe};
+use wasm_bindgen::prelude::*;
+
+// Well-known patterns available to `Universe::insert_pattern`
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    Glider = 0,          // 3x3 spaceship moving one cell diagonally every 4 ticks
+    Blinker = 1,         // 3x1 oscillator with period 2
+    Toad = 2,            // 4x2 oscillator with period 2
+    Beacon = 3,          // 4x4 oscillator with period 2
+    Pulsar = 4,          // 13x13 oscillator with period 3
+    GosperGliderGun = 5, // 36x9 gun emitting a glider every 30 ticks
+}
+
+impl Pattern {
+    // Rows of the pattern, with 'O' marking live cells
+    fn rows(self) -> &'static [&'static str] {
+        match self {
+            Pattern::Glider => &[".O.", "..O", "OOO"],
+            Pattern::Blinker => &["OOO"],
+            Pattern::Toad => &[".OOO", "OOO."],
+            Pattern::Beacon => &["OO..", "O...", "...O", "..OO"],
+            Pattern::Pulsar => &[
+                "..OOO...OOO..",
+                ".............",
+                "O....O.O....O",
+                "O....O.O....O",
+                "O....O.O....O",
+                "..OOO...OOO..",
+                ".............",
+                "..OOO...OOO..",
+                "O....O.O....O",
+                "O....O.O....O",
+                "O....O.O....O",
+                ".............",
+                "..OOO...OOO..",
+            ],
+            Pattern::GosperGliderGun => &[
+                "........................O...........",
+                "......................O.O...........",
+                "............OO......OO............OO",
+                "...........O...O....OO............OO",
+                "OO........O.....O...OO..............",
+                "OO........O...O.OO....O.O...........",
+                "..........O.....O.......O...........",
+                "...........O...O....................",
+                "............OO......................",
+            ],
+        }
+    }
+
+    // (row, col) offsets of the pattern's live cells relative to its top-left corner
+    pub(crate) fn offsets(self) -> impl Iterator<Item = (u32, u32)> {
+        self.rows().iter().enumerate().flat_map(|(row, line)| {
+            line.bytes()
+                .enumerate()
+                .filter(|&(_, b)| b == b'O')
+                .map(move |(col, _)| (row as u32, col as u32))
+        })
+    }
+
+    // Offsets after mirroring left to right (if `mirror`) and then rotating
+    // `rotation` quarter turns clockwise, still relative to the top-left
+    // corner of the transformed bounding box
+    fn transformed_offsets(self, rotation: u8, mirror: bool) -> Vec<(u32, u32)> {
+        let rows = self.rows();
+        let (mut width, mut height) = (rows[0].len() as u32, rows.len() as u32);
+        let mut offsets: Vec<(u32, u32)> = self
+            .offsets()
+            .map(|(row, col)| {
+                if mirror {
+                    (row, width - 1 - col)
+                } else {
+                    (row, col)
+                }
+            })
+            .collect();
+        for _ in 0..rotation % 4 {
+            for offset in offsets.iter_mut() {
+                *offset = (offset.1, height - 1 - offset.0);
+            }
+            std::mem::swap(&mut width, &mut height);
+        }
+        offsets
+    }
+}
+
+impl Universe {
+    // Writes live cells at `offsets` from (row, col), wrapping around the
+    // edges, as a single undo step
+    fn stamp_offsets(&mut self, row: u32, col: u32, offsets: impl Iterator<Item = (u32, u32)>) {
+        self.record_history();
+        for (delta_row, delta_col) in offsets {
+            self.write_cell(
+                (row % self.height + delta_row) % self.height,
+                (col % self.width + delta_col) % self.width,
+                Cell::Alive,
+            );
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    // Stamps a built-in pattern with its bounding box's top-left corner at
+    // (row, col), wrapping around the edges. Only the pattern's live cells are
+    // written; everything else is left untouched.
+    pub fn insert_pattern(&mut self, pattern: Pattern, row: u32, col: u32) {
+        self.stamp_offsets(row, col, pattern.offsets());
+    }
+
+    // Stamps a built-in pattern like `insert_pattern`, first mirroring it left
+    // to right (if `mirror`) and then rotating it `rotation` quarter turns
+    // clockwise (0-3 for 0°, 90°, 180° and 270°; larger values wrap). (row,
+    // col) is the top-left corner of the transformed pattern.
+    pub fn insert_pattern_transformed(
+        &mut self,
+        pattern: Pattern,
+        row: u32,
+        col: u32,
+        rotation: u8,
+        mirror: bool,
+    ) {
+        let offsets = pattern.transformed_offsets(rotation, mirror);
+        self.stamp_offsets(row, col, offsets.into_iter());
+    }
+
+    // ORs another board onto this one with its top-left corner at (row, col):
+    // every live cell in `other` comes alive here and everything else is left
+    // untouched. The boards can be any size; cells past the edges wrap around
+    // like `insert_pattern`.
+    pub fn overlay(&mut self, other: &Universe, row: u32, col: u32) {
+        self.stamp_offsets(row, col, other.live_cells_iter());
+    }
+
+    // Stamps a multi-line ASCII drawing with its top-left corner at
+    // (row, col), setting every `alive_char` alive and leaving all other
+    // characters (and the space past the end of shorter lines) untouched.
+    // Wraps around the edges like `insert_pattern`.
+    pub fn insert_ascii(&mut self, art: &str, row: u32, col: u32, alive_char: char) {
+        let offsets: Vec<(u32, u32)> = art
+            .lines()
+            .enumerate()
+            .flat_map(|(r, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(move |&(_, ch)| ch == alive_char)
+                    .map(move |(c, _)| (r as u32, c as u32))
+            })
+            .collect();
+        self.stamp_offsets(row, col, offsets.into_iter());
+    }
+}
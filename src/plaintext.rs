@@ -0,0 +1,49 @@
+// Plaintext `.cells` pattern import and export
+//
+// Each line is a row of the pattern with `O` for a live cell and `.` for a
+// dead one. Lines starting with `!` are comments.
+
+use crate::Universe;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl Universe {
+    // Replaces the board with a plaintext pattern placed at the top-left
+    // corner. The board is cleared first and grown if the pattern doesn't fit.
+    // Any character other than `O` is treated as a dead cell.
+    pub fn load_plaintext(&mut self, text: &str) {
+        let rows: Vec<&str> = text.lines().filter(|line| !line.starts_with('!')).collect();
+
+        let mut cells = Vec::new();
+        let mut width = 0;
+        for (row, line) in rows.iter().enumerate() {
+            let line = line.trim_end();
+            width = width.max(line.chars().count() as u32);
+            for (col, ch) in line.chars().enumerate() {
+                if ch == 'O' {
+                    cells.push((row as u32, col as u32));
+                }
+            }
+        }
+
+        // Trailing blank lines don't add any rows to the pattern
+        let height = cells.iter().map(|&(row, _)| row + 1).max().unwrap_or(0);
+        self.load_cells(width, height, &cells);
+    }
+
+    // Encodes the live cells' bounding box as plaintext rows. An empty board
+    // produces an empty string.
+    pub fn to_plaintext(&self) -> String {
+        let mut text = String::new();
+        if let Some((min_row, min_col, max_row, max_col)) = self.live_extent() {
+            for row in min_row..=max_row {
+                for col in min_col..=max_col {
+                    let alive = self.cells[self.get_index(row, col)];
+                    text.push(if alive { 'O' } else { '.' });
+                }
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
@@ -0,0 +1,125 @@
+// Renderers beyond the plain `Display` string
+
+use crate::{Cell, Universe};
+use wasm_bindgen::prelude::*;
+
+// ANSI escape sequences used by `render_ansi`
+const ANSI_ALIVE: &str = "\x1b[42m"; // Green background
+const ANSI_DEAD: &str = "\x1b[49m"; // Default background
+const ANSI_RESET: &str = "\x1b[0m";
+
+#[wasm_bindgen]
+impl Universe {
+    // Renders the board into a `width * height * 4` RGBA pixel buffer, one
+    // pixel per cell in row-major order, ready to wrap in an `ImageData` and
+    // blit to a canvas. Colors are packed as 0xRRGGBBAA.
+    pub fn render_rgba(&self, alive: u32, dead: u32) -> Vec<u8> {
+        let (alive, dead) = (alive.to_be_bytes(), dead.to_be_bytes());
+        let mut pixels = Vec::with_capacity(self.cells.len() * 4);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let alive_cell = self.cells[self.get_index(row, col)];
+                pixels.extend_from_slice(if alive_cell { &alive } else { &dead });
+            }
+        }
+        pixels
+    }
+
+    // Renders the board for a terminal: live cells are green blocks and dead
+    // cells are blanks, with the colors reset at the end of every line. The
+    // output is only meaningful in a terminal that understands ANSI escapes.
+    pub fn render_ansi(&self) -> String {
+        let mut out = String::new();
+        for row in 0..self.height {
+            // Only emit a color change when the state differs from the last cell
+            let mut current = None;
+            for col in 0..self.width {
+                let alive = self.cells[self.get_index(row, col)];
+                if current != Some(alive) {
+                    out.push_str(if alive { ANSI_ALIVE } else { ANSI_DEAD });
+                    current = Some(alive);
+                }
+                out.push(' ');
+            }
+            out.push_str(ANSI_RESET);
+            out.push('\n');
+        }
+        out
+    }
+
+    // Renders the board with each cell marked by what the next tick does to
+    // it: `+` born, `-` dies, `#` stays alive and `.` stays dead. Handy for
+    // seeing how a rule change plays out. Rows end with a newline like
+    // `render`. An auto-expanding board is diffed at its current size.
+    pub fn diff_next(&self) -> String {
+        let mut next = Vec::new();
+        self.tick_into(&mut next);
+        let mut out = String::with_capacity(self.cells.len() + self.height as usize);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let alive = self.cells[self.get_index(row, col)];
+                let next_alive = next[(row * self.width + col) as usize] == Cell::Alive;
+                out.push(match (alive, next_alive) {
+                    (false, true) => '+',
+                    (true, false) => '-',
+                    (true, true) => '#',
+                    (false, false) => '.',
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    // Encodes the board as an ASCII Netpbm bitmap (PBM, "P1"): a
+    // `P1\n<width> <height>\n` header followed by one line per row with `1`
+    // (black) for a live cell and `0` for a dead one. Tools like ImageMagick
+    // can turn it straight into a PNG.
+    pub fn to_pbm(&self) -> String {
+        let mut out = format!("P1\n{} {}\n", self.width, self.height);
+        out.reserve(self.cells.len() + self.height as usize);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                out.push(if self.cells[self.get_index(row, col)] {
+                    '1'
+                } else {
+                    '0'
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    // Shrinks the board to an `out_w` x `out_h` grayscale thumbnail, one byte
+    // per output pixel in row-major order. Each pixel covers a block of the
+    // board and its value is the block's fraction of live cells scaled to
+    // 0-255, so a half-alive block comes out mid-gray. Upscaling repeats
+    // source cells instead.
+    pub fn downsample(&self, out_w: u32, out_h: u32) -> Vec<u8> {
+        // Source range covered by output pixel `i` of `out` along an axis of
+        // `size` cells, never empty
+        let span = |i: u32, out: u32, size: u32| {
+            let start = (i as u64 * size as u64 / out as u64) as u32;
+            let end = ((i as u64 + 1) * size as u64 / out as u64) as u32;
+            start..end.max(start + 1)
+        };
+
+        let mut pixels = Vec::with_capacity((out_w * out_h) as usize);
+        for out_row in 0..out_h {
+            let rows = span(out_row, out_h, self.height);
+            for out_col in 0..out_w {
+                let cols = span(out_col, out_w, self.width);
+                let mut alive = 0u64;
+                for row in rows.clone() {
+                    for col in cols.clone() {
+                        alive += self.cells[self.get_index(row, col)] as u64;
+                    }
+                }
+                let total = (rows.len() * cols.len()) as u64;
+                pixels.push(((alive * 255 + total / 2) / total) as u8);
+            }
+        }
+        pixels
+    }
+}
@@ -0,0 +1,208 @@
+// Run Length Encoded (RLE) pattern import and export
+//
+// An RLE file is an optional `#` comment block, a header line such as
+// `x = 3, y = 3, rule = B3/S23`, and a body of `<count><tag>` runs where `b`
+// is a dead cell, `o` a live cell, `$` ends a row and `!` ends the pattern.
+
+use crate::rule::Rule;
+use crate::{GameError, Universe};
+use wasm_bindgen::prelude::*;
+
+// The RLE spec asks for body lines of at most 70 characters
+const MAX_LINE_LENGTH: usize = 70;
+
+const TOO_LARGE: &str = "RLE pattern is too large";
+
+// A pattern decoded from RLE: its declared size, rule and live cells
+pub(crate) struct RlePattern {
+    pub width: u32,
+    pub height: u32,
+    pub rule: Option<Rule>,
+    pub cells: Vec<(u32, u32)>, // (row, col) of every live cell
+}
+
+// Parses an RLE string. Run counts may have several digits, comment lines
+// start with `#` and the trailing `!` is optional.
+pub(crate) fn parse(rle: &str) -> Result<RlePattern, String> {
+    let mut pattern = RlePattern {
+        width: 0,
+        height: 0,
+        rule: None,
+        cells: Vec::new(),
+    };
+    let (mut row, mut col) = (0u32, 0u32);
+    let mut run: Option<u32> = None;
+
+    'lines: for line in rle.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('x') {
+            parse_header(line, &mut pattern)?;
+            continue;
+        }
+
+        for ch in line.chars() {
+            match ch {
+                '0'..='9' => {
+                    let digit = ch.to_digit(10).unwrap();
+                    let count = run.unwrap_or(0);
+                    run = Some(
+                        count
+                            .checked_mul(10)
+                            .and_then(|n| n.checked_add(digit))
+                            .ok_or("run count is too large")?,
+                    );
+                }
+                'b' | '.' => {
+                    col = col.checked_add(run.take().unwrap_or(1)).ok_or(TOO_LARGE)?;
+                }
+                'o' => {
+                    let end = col.checked_add(run.take().unwrap_or(1)).ok_or(TOO_LARGE)?;
+                    // The board holding these cells must have an indexable size
+                    row.checked_add(1)
+                        .and_then(|rows| rows.checked_mul(end))
+                        .ok_or(TOO_LARGE)?;
+                    pattern.cells.extend((col..end).map(|c| (row, c)));
+                    col = end;
+                }
+                '$' => {
+                    row = row.checked_add(run.take().unwrap_or(1)).ok_or(TOO_LARGE)?;
+                    col = 0;
+                }
+                '!' => break 'lines,
+                c if c.is_whitespace() => {}
+                c => return Err(format!("unexpected character '{}' in RLE body", c)),
+            }
+        }
+    }
+
+    // The header is only a hint; grow it to fit whatever the body described.
+    for &(r, c) in pattern.cells.iter() {
+        pattern.width = pattern.width.max(c + 1);
+        pattern.height = pattern.height.max(r + 1);
+    }
+    pattern.width.checked_mul(pattern.height).ok_or(TOO_LARGE)?;
+    Ok(pattern)
+}
+
+// Reads the `x = .., y = .., rule = ..` header line
+fn parse_header(line: &str, pattern: &mut RlePattern) -> Result<(), String> {
+    for field in line.split(',') {
+        let mut parts = field.splitn(2, '=');
+        let key = parts.next().unwrap_or("").trim();
+        let value = parts
+            .next()
+            .ok_or_else(|| format!("malformed RLE header field \"{}\"", field.trim()))?
+            .trim();
+        match key {
+            "x" => pattern.width = parse_dimension(value)?,
+            "y" => pattern.height = parse_dimension(value)?,
+            "rule" => pattern.rule = Some(value.parse()?),
+            _ => {} // Unknown header fields are ignored
+        }
+    }
+    Ok(())
+}
+
+fn parse_dimension(value: &str) -> Result<u32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid RLE dimension \"{}\"", value))
+}
+
+#[wasm_bindgen]
+impl Universe {
+    // Replaces the board with an RLE pattern placed at the top-left corner.
+    // The board is cleared first and grown if the pattern doesn't fit; a
+    // `rule` in the header replaces the current rule.
+    pub fn load_rle(&mut self, rle: &str) -> Result<(), GameError> {
+        let pattern = parse(rle).map_err(GameError::Parse)?;
+        self.check_pattern_fits(pattern.width, pattern.height)?;
+
+        self.load_cells(pattern.width, pattern.height, &pattern.cells);
+        if let Some(rule) = pattern.rule {
+            self.rule = rule;
+        }
+        Ok(())
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    // Encodes the live cells as RLE. The header gives the size of the live
+    // cells' bounding box and the current rule; trailing dead cells in a row
+    // are omitted and runs of empty rows collapse into a single `<n>$`.
+    pub fn to_rle(&self) -> String {
+        let (min_row, min_col, max_row, max_col) = match self.live_extent() {
+            Some(bounds) => bounds,
+            None => return format!("x = 0, y = 0, rule = {}\n!\n", self.rule),
+        };
+
+        // Collect `<count><tag>` runs, deferring row ends so empty rows merge
+        let mut runs: Vec<String> = Vec::new();
+        let mut pending_rows = 0;
+        for row in min_row..=max_row {
+            let mut row_runs: Vec<(u32, char)> = Vec::new();
+            for col in min_col..=max_col {
+                let tag = if self.cells[self.get_index(row, col)] {
+                    'o'
+                } else {
+                    'b'
+                };
+                match row_runs.last_mut() {
+                    Some((count, last)) if *last == tag => *count += 1,
+                    _ => row_runs.push((1, tag)),
+                }
+            }
+            // Trailing dead cells are implied by the end of the row
+            if let Some(&(_, 'b')) = row_runs.last() {
+                row_runs.pop();
+            }
+            if row_runs.is_empty() {
+                pending_rows += 1;
+                continue;
+            }
+
+            if row > min_row {
+                runs.push(encode_run(pending_rows + 1, '$'));
+            }
+            pending_rows = 0;
+            runs.extend(
+                row_runs
+                    .into_iter()
+                    .map(|(count, tag)| encode_run(count, tag)),
+            );
+        }
+        runs.push("!".to_string());
+
+        // Wrap the body so no line exceeds the spec's length limit
+        let mut rle = format!(
+            "x = {}, y = {}, rule = {}\n",
+            max_col - min_col + 1,
+            max_row - min_row + 1,
+            self.rule
+        );
+        let mut line_length = 0;
+        for run in runs {
+            if line_length + run.len() > MAX_LINE_LENGTH {
+                rle.push('\n');
+                line_length = 0;
+            }
+            line_length += run.len();
+            rle.push_str(&run);
+        }
+        rle.push('\n');
+        rle
+    }
+}
+
+// A single run, leaving out the count when it is 1
+fn encode_run(count: u32, tag: char) -> String {
+    if count == 1 {
+        tag.to_string()
+    } else {
+        format!("{}{}", count, tag)
+    }
+}
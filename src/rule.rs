@@ -0,0 +1,145 @@
+// Birth/survival rule sets in B/S notation (e.g. Conway's Life is B3/S23)
+
+use crate::Cell;
+use std::fmt;
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+// Each rule stores two bitmasks where bit `n` is set when a cell with `n`
+// live neighbors is born (for dead cells) or survives (for live cells). The
+// masks are wide enough for the largest supported neighborhood radius.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rule {
+    birth: u128,
+    survival: u128,
+}
+
+// Highest neighbor count a rule can refer to
+const MAX_COUNT: u8 = 127;
+
+impl Rule {
+    // Conway's Game of Life: B3/S23
+    pub const CONWAY: Rule = Rule {
+        birth: 1 << 3,
+        survival: (1 << 2) | (1 << 3),
+    };
+
+    // Builds a rule from lists of neighbor counts. Counts above 127 can never
+    // occur, even with the largest neighborhood radius, and are ignored.
+    pub fn from_counts(birth: &[u8], survival: &[u8]) -> Rule {
+        Rule {
+            birth: Rule::mask(birth),
+            survival: Rule::mask(survival),
+        }
+    }
+
+    fn mask(counts: &[u8]) -> u128 {
+        counts
+            .iter()
+            .filter(|&&n| n <= MAX_COUNT)
+            .fold(0, |mask, &n| mask | (1 << n))
+    }
+
+    // Applies the rule to a single cell given its live neighbor count
+    pub fn next_cell(&self, cell: Cell, live_neighbors: u8) -> Cell {
+        let bit = if live_neighbors <= MAX_COUNT {
+            1 << live_neighbors
+        } else {
+            0
+        };
+        match cell {
+            // A live cell survives only if its neighbor count is in the survival set
+            Cell::Alive if self.survival & bit != 0 => Cell::Alive,
+            Cell::Alive => Cell::Dead,
+            // A dead cell is born only if its neighbor count is in the birth set
+            Cell::Dead if self.birth & bit != 0 => Cell::Alive,
+            Cell::Dead => Cell::Dead,
+        }
+    }
+}
+
+// Well-known rules that can be selected by name instead of B/S notation
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RulePreset {
+    Conway = 0,     // B3/S23
+    HighLife = 1,   // B36/S23, has a self-replicating pattern
+    DayNight = 2,   // B3678/S34678, symmetric between live and dead cells
+    Seeds = 3,      // B2/S, every live cell dies each tick
+    Replicator = 4, // B1357/S1357, every pattern copies itself
+}
+
+impl RulePreset {
+    // The birth/survival rule the preset stands for
+    pub fn rule(self) -> Rule {
+        match self {
+            RulePreset::Conway => Rule::CONWAY,
+            RulePreset::HighLife => Rule::from_counts(&[3, 6], &[2, 3]),
+            RulePreset::DayNight => Rule::from_counts(&[3, 6, 7, 8], &[3, 4, 6, 7, 8]),
+            RulePreset::Seeds => Rule::from_counts(&[2], &[]),
+            RulePreset::Replicator => Rule::from_counts(&[1, 3, 5, 7], &[1, 3, 5, 7]),
+        }
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::CONWAY
+    }
+}
+
+// Parses standard `Bxxx/Sxxx` notation, e.g. "B3/S23" or "b36/s23".
+// Surrounding whitespace is ignored and either half may be empty ("B2/S").
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(rule: &str) -> Result<Rule, String> {
+        let rule = rule.trim().to_ascii_uppercase();
+        let mut parts = rule.split('/');
+        let (birth, survival) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(b), Some(s), None) if b.starts_with('B') && s.starts_with('S') => (b, s),
+            _ => return Err(format!("expected a rule like \"B3/S23\", got \"{}\"", rule)),
+        };
+
+        Ok(Rule {
+            birth: Rule::parse_counts(&birth[1..])?,
+            survival: Rule::parse_counts(&survival[1..])?,
+        })
+    }
+}
+
+impl Rule {
+    // Turns a run of neighbor-count digits like "23" into a bitmask
+    fn parse_counts(digits: &str) -> Result<u128, String> {
+        let mut mask = 0;
+        for ch in digits.chars() {
+            let n = match ch.to_digit(10) {
+                Some(n) if n <= 8 => n,
+                _ => return Err(format!("invalid neighbor count '{}', expected 0-8", ch)),
+            };
+            if mask & (1 << n) != 0 {
+                return Err(format!("duplicate neighbor count '{}'", ch));
+            }
+            mask |= 1 << n;
+        }
+        Ok(mask)
+    }
+}
+
+// Formats the rule back into `Bxxx/Sxxx` notation. Counts above 8 (only
+// reachable with a larger neighborhood radius) have no single-digit form and
+// are left out.
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "B")?;
+        for n in (0..=8).filter(|n| self.birth & (1 << n) != 0) {
+            write!(f, "{}", n)?;
+        }
+        write!(f, "/S")?;
+        for n in (0..=8).filter(|n| self.survival & (1 << n) != 0) {
+            write!(f, "{}", n)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+// Sparse storage for huge, mostly empty boards
+//
+// Instead of one bit per cell, `SparseUniverse` keeps only the coordinates
+// of the live cells, so a single glider on an effectively unbounded plane
+// costs a handful of entries rather than megabytes of dead cells.
+
+use crate::rule::Rule;
+use crate::{Cell, GameError, Pattern};
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::prelude::*;
+
+// An unbounded universe storing the set of live cells. Coordinates are
+// signed and there are no edges, so patterns never wrap or fall off.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+pub struct SparseUniverse {
+    cells: HashSet<(i64, i64)>, // (row, col) of every live cell
+    generation: u64,            // Number of ticks since creation
+    rule: Rule,                 // Birth/survival counts applied on each tick
+}
+
+#[wasm_bindgen]
+impl SparseUniverse {
+    // Creates an empty plane running Conway's B3/S23
+    pub fn new() -> SparseUniverse {
+        SparseUniverse::default()
+    }
+
+    // Advances the plane by one tick. Every live cell adds one to the count of
+    // each of its 8 neighbors, so only cells next to a live one are visited.
+    // Birth with 0 neighbors (`B0`) would fill the whole plane and is ignored.
+    pub fn tick(&mut self) {
+        let mut counts: HashMap<(i64, i64), u8> = HashMap::new();
+        for &(row, col) in &self.cells {
+            for delta_row in -1..=1 {
+                for delta_col in -1..=1 {
+                    if delta_row != 0 || delta_col != 0 {
+                        *counts
+                            .entry((row + delta_row, col + delta_col))
+                            .or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+
+        // Live cells with no neighbors never show up in `counts`
+        let isolated = self
+            .cells
+            .iter()
+            .filter(|cell| !counts.contains_key(cell))
+            .map(|&cell| (cell, 0));
+        let next = counts
+            .iter()
+            .map(|(&cell, &count)| (cell, count))
+            .chain(isolated)
+            .filter(|&(cell, count)| {
+                let state = Cell::from(self.cells.contains(&cell));
+                self.rule.next_cell(state, count).is_alive()
+            })
+            .map(|(cell, _)| cell)
+            .collect();
+
+        self.cells = next;
+        self.generation += 1;
+    }
+
+    // Advances the plane by `n` ticks
+    pub fn tick_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.tick();
+        }
+    }
+
+    pub fn population(&self) -> u32 {
+        self.cells.len() as u32
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn get_cell(&self, row: i64, col: i64) -> Cell {
+        Cell::from(self.cells.contains(&(row, col)))
+    }
+
+    pub fn set_cell(&mut self, row: i64, col: i64, cell: Cell) {
+        match cell {
+            Cell::Alive => self.cells.insert((row, col)),
+            Cell::Dead => self.cells.remove(&(row, col)),
+        };
+    }
+
+    // Stamps a built-in pattern with its bounding box's top-left corner at
+    // (row, col), like `Universe::insert_pattern` but without wrapping
+    pub fn insert_pattern(&mut self, pattern: Pattern, row: i64, col: i64) {
+        for (delta_row, delta_col) in pattern.offsets() {
+            self.cells
+                .insert((row + delta_row as i64, col + delta_col as i64));
+        }
+    }
+
+    // Replaces the rule using B/S notation, e.g. "B36/S23" for HighLife
+    pub fn set_rule_string(&mut self, rule: &str) -> Result<(), GameError> {
+        self.rule = rule.parse().map_err(GameError::Parse)?;
+        Ok(())
+    }
+
+    // Coordinates of the live cells as flattened (row, col) pairs, sorted
+    // row by row
+    pub fn live_cells(&self) -> Vec<i64> {
+        let mut cells: Vec<(i64, i64)> = self.cells.iter().cloned().collect();
+        cells.sort_unstable();
+        cells
+            .into_iter()
+            .flat_map(|(row, col)| [row, col])
+            .collect()
+    }
+}
@@ -0,0 +1,118 @@
+// Whole-board transforms: rotations, reflections, translations and growing
+// the board around its live cells
+
+use crate::{check_dimensions, Universe};
+use fixedbitset::FixedBitSet;
+use wasm_bindgen::prelude::*;
+
+// Dead cells added beyond a live cell that reaches an edge in auto-expand mode
+const AUTO_EXPAND_MARGIN: u32 = 8;
+
+impl Universe {
+    // Rebuilds the cells with every (row, col) moved to `map(row, col)` on a
+    // grid of the new dimensions. Immortal cells, dying cells and Wireworld
+    // wires move along with them.
+    fn remap<F: Fn(u32, u32) -> (u32, u32)>(&mut self, new_width: u32, new_height: u32, map: F) {
+        check_dimensions(new_width, new_height);
+        let mut cells = FixedBitSet::with_capacity((new_width * new_height) as usize);
+        for (row, col) in self.live_cells_iter() {
+            let (new_row, new_col) = map(row, col);
+            cells.insert(self.order.index(new_width, new_height, new_row, new_col));
+        }
+
+        let mut immortal = FixedBitSet::new();
+        if self.immortal.len() == self.cells.len() {
+            immortal.grow(cells.len());
+            for idx in self.immortal.ones() {
+                let (row, col) = self.get_coords(idx);
+                let (new_row, new_col) = map(row, col);
+                immortal.insert(self.order.index(new_width, new_height, new_row, new_col));
+            }
+        }
+
+        let mut decay = vec![0; cells.len()];
+        for (idx, &state) in self.decay.iter().enumerate() {
+            if state > 0 {
+                let (row, col) = self.get_coords(idx);
+                let (new_row, new_col) = map(row, col);
+                decay[self.order.index(new_width, new_height, new_row, new_col)] = state;
+            }
+        }
+
+        self.width = new_width;
+        self.height = new_height;
+        self.cells = cells;
+        self.immortal = immortal;
+        self.reset_tracking_with_decay(decay);
+    }
+
+    // Grows the board by `AUTO_EXPAND_MARGIN` past every edge a live cell
+    // touches, shifting the cells so nothing moves relative to each other
+    pub(crate) fn expand_to_fit(&mut self) {
+        let (min_row, min_col, max_row, max_col) = match self.live_extent() {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        let margin = |touches: bool| if touches { AUTO_EXPAND_MARGIN } else { 0 };
+        let top = margin(min_row == 0);
+        let left = margin(min_col == 0);
+        let bottom = margin(max_row == self.height - 1);
+        let right = margin(max_col == self.width - 1);
+        if top + left + bottom + right == 0 {
+            return;
+        }
+
+        let new_width = self.width + left + right;
+        let new_height = self.height + top + bottom;
+        self.remap(new_width, new_height, |row, col| (row + top, col + left));
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    // Rotates the whole board 90° clockwise, swapping width and height
+    pub fn rotate_cw(&mut self) {
+        let height = self.height;
+        self.remap(self.height, self.width, |row, col| (col, height - 1 - row));
+    }
+
+    // Mirrors the board left to right
+    pub fn flip_horizontal(&mut self) {
+        let width = self.width;
+        self.remap(self.width, self.height, |row, col| (row, width - 1 - col));
+    }
+
+    // Mirrors the board top to bottom
+    pub fn flip_vertical(&mut self) {
+        let height = self.height;
+        self.remap(self.width, self.height, |row, col| (height - 1 - row, col));
+    }
+
+    // Shifts every cell by (dr, dc), wrapping around the edges like the
+    // toroidal neighbor counting. Negative offsets shift up and left.
+    pub fn translate(&mut self, dr: i32, dc: i32) {
+        let (width, height) = (self.width as i64, self.height as i64);
+        self.remap(self.width, self.height, |row, col| {
+            (
+                (row as i64 + dr as i64).rem_euclid(height) as u32,
+                (col as i64 + dc as i64).rem_euclid(width) as u32,
+            )
+        });
+    }
+
+    pub fn auto_expand(&self) -> bool {
+        self.auto_expand
+    }
+
+    // Emulates an infinite plane: after every tick, any edge a live cell has
+    // reached is pushed out by a few cells, so patterns neither wrap nor die
+    // at the border. The generation and undo history carry on across
+    // expansions, but checkpoints from a smaller board can no longer be
+    // restored and ages restart as after a `resize`.
+    pub fn set_auto_expand(&mut self, enabled: bool) {
+        self.auto_expand = enabled;
+        if enabled {
+            self.expand_to_fit();
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// Wireworld, a four-state automaton for simulating digital circuits. Cells
+// are empty, conductor, electron head or electron tail:
+//
+//   head      -> tail
+//   tail      -> conductor
+//   conductor -> head if 1 or 2 neighbors are heads, else conductor
+//   empty     -> empty
+//
+// Heads are the live cells in the bitset, so the usual neighbor counting
+// finds them. Tails and conductors are kept in the per-cell state buffer
+// shared with Generations rules, which makes `cell_state` and
+// `set_cell_state` read and write Wireworld states as 0 (empty),
+// 1 (conductor), 2 (tail) and 3 (head).
+
+use crate::{Cell, Universe};
+use wasm_bindgen::prelude::*;
+
+// Per-cell states of the non-head cells
+const CONDUCTOR: u8 = 1;
+const TAIL: u8 = 2;
+
+// Which transition rules `tick` applies
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Automaton {
+    Life = 0,      // Birth/survival rules, optionally with Generations decay
+    Wireworld = 1, // Electrons moving along conductors
+}
+
+impl Universe {
+    // Whether the cell at `idx` is an electron head next tick, without
+    // touching any state
+    pub(crate) fn wireworld_cell(&self, idx: usize, row: u32, col: u32) -> Cell {
+        let heads = match self.decay[idx] {
+            CONDUCTOR if !self.cells[idx] => self.live_neighbor_count(row, col),
+            _ => 0,
+        };
+        Cell::from(heads == 1 || heads == 2)
+    }
+
+    // Whether a tick leaves the stored state of the non-head cell at `idx`
+    // alone: tails turn back into conductors, everything else waits on heads
+    pub(crate) fn wireworld_state_settled(&self, idx: usize) -> bool {
+        self.decay[idx] != TAIL
+    }
+
+    // Wireworld transition for the cell at `idx`, updating its stored state
+    // for everything but heads
+    pub(crate) fn next_wireworld_cell(&mut self, idx: usize, row: u32, col: u32) -> Cell {
+        let next = self.wireworld_cell(idx, row, col);
+        self.decay[idx] = match (self.cells[idx], self.decay[idx], next) {
+            (true, _, _) => TAIL,
+            (false, TAIL, _) => CONDUCTOR,
+            (false, _, Cell::Alive) => 0,
+            (false, state, Cell::Dead) => state,
+        };
+        next
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    pub fn automaton(&self) -> Automaton {
+        self.automaton
+    }
+
+    // Switches between Life-like rules and Wireworld. Wireworld uses 4 cell
+    // states, so `states()` becomes 4; switching back to Life returns to the
+    // 2 states of plain Life. Existing live cells carry over as electron
+    // heads (and vice versa).
+    pub fn set_automaton(&mut self, automaton: Automaton) {
+        self.automaton = automaton;
+        self.wake_all();
+        match automaton {
+            Automaton::Life => self.set_states(2),
+            Automaton::Wireworld => self.states = 4,
+        }
+    }
+}
@@ -5,9 +5,2733 @@
 extern crate wasm_bindgen_test;
 use wasm_bindgen_test::*;
 
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{
+    init, Automaton, Bounds, Cell, Centroid, GameError, HashlifeUniverse, Neighborhood, Pattern,
+    RulePreset, SparseUniverse, Stats, StorageOrder, Topology, Universe, UniverseBuilder,
+};
+
 wasm_bindgen_test_configure!(run_in_browser);
 
+// Reads a single cell back out of the rendered string.
+fn is_alive_in_render(universe: &Universe, row: u32, col: u32) -> bool {
+    let line = universe
+        .render()
+        .lines()
+        .nth(row as usize)
+        .unwrap()
+        .to_string();
+    line.chars().nth(col as usize).unwrap() == '◼'
+}
+
+// Whether the cell at (row, col) is alive.
+fn is_alive(universe: &Universe, row: u32, col: u32) -> bool {
+    universe.get_cell(row, col) == Cell::Alive
+}
+
+// Number of cells the rendered string contains.
+fn rendered_cell_count(universe: &Universe) -> u32 {
+    universe.render().chars().filter(|&c| c != '\n').count() as u32
+}
+
+// A 5x5 universe with a vertical blinker hugging the left edge.
+fn edge_blinker() -> Universe {
+    let mut universe = Universe::new_with_size(5, 5);
+    universe.clear();
+    universe.set_cell(1, 0, Cell::Alive).unwrap();
+    universe.set_cell(2, 0, Cell::Alive).unwrap();
+    universe.set_cell(3, 0, Cell::Alive).unwrap();
+    universe
+}
+
+// Sets the `O` cells of an ASCII pattern alive with its top-left at (row, col),
+// wrapping around the edges.
+fn stamp(universe: &mut Universe, row: u32, col: u32, pattern: &[&str]) {
+    let (width, height) = (universe.width(), universe.height());
+    for (r, line) in pattern.iter().enumerate() {
+        for (c, ch) in line.chars().enumerate() {
+            if ch == 'O' {
+                universe
+                    .set_cell(
+                        (row + r as u32) % height,
+                        (col + c as u32) % width,
+                        Cell::Alive,
+                    )
+                    .unwrap();
+            }
+        }
+    }
+}
+
+// Checks that the cells covered by an ASCII pattern match it exactly.
+fn matches_pattern(universe: &Universe, row: u32, col: u32, pattern: &[&str]) -> bool {
+    pattern.iter().enumerate().all(|(r, line)| {
+        line.chars().enumerate().all(|(c, ch)| {
+            is_alive_in_render(universe, row + r as u32, col + c as u32) == (ch == 'O')
+        })
+    })
+}
+
+const REPLICATOR: [&str; 5] = ["..OOO", ".O..O", "O...O", "O..O.", "OOO.."];
+
+// Reference byte-per-cell Conway tick on a torus, used to check the bitset engine.
+fn reference_tick(width: usize, height: usize, cells: &[u8]) -> Vec<u8> {
+    let mut next = cells.to_vec();
+    for row in 0..height {
+        for col in 0..width {
+            let mut count = 0;
+            for dr in [height - 1, 0, 1] {
+                for dc in [width - 1, 0, 1] {
+                    if dr == 0 && dc == 0 {
+                        continue;
+                    }
+                    count += cells[((row + dr) % height) * width + (col + dc) % width];
+                }
+            }
+            let alive = cells[row * width + col] == 1;
+            next[row * width + col] = (count == 3 || (alive && count == 2)) as u8;
+        }
+    }
+    next
+}
+
+// Reads every cell into a byte per cell via the rendered string.
+fn render_bytes(universe: &Universe) -> Vec<u8> {
+    universe
+        .render()
+        .chars()
+        .filter(|&c| c != '\n')
+        .map(|c| (c == '◼') as u8)
+        .collect()
+}
+
+const GLIDER: [&str; 3] = [".O.", "..O", "OOO"];
+
+// A universe containing a single 2x2 block, the simplest still life.
+fn block_universe() -> Universe {
+    let mut universe = Universe::new_with_size(6, 6);
+    universe.clear();
+    stamp(&mut universe, 2, 2, &["OO", "OO"]);
+    universe
+}
+
 #[wasm_bindgen_test]
 fn pass() {
     assert_eq!(1 + 1, 2);
 }
+
+#[wasm_bindgen_test]
+fn test_toggle_cell_twice_restores_state() {
+    let mut universe = Universe::new();
+    let original = universe.render();
+
+    universe.toggle_cell(3, 4);
+    assert_ne!(universe.render(), original);
+
+    universe.toggle_cell(3, 4);
+    assert_eq!(universe.render(), original);
+}
+
+#[wasm_bindgen_test]
+fn test_toggle_cell_wraps_out_of_range() {
+    let mut universe = Universe::new();
+    let mut wrapped = Universe::new();
+
+    universe.toggle_cell(1, 2);
+    wrapped.toggle_cell(1 + universe.height(), 2 + universe.width());
+    assert_eq!(universe.render(), wrapped.render());
+}
+
+#[wasm_bindgen_test]
+fn test_set_cell_is_counted_as_neighbor() {
+    let mut universe = Universe::new();
+
+    // Kill the 3x3 block around (10, 10), then revive exactly three neighbors.
+    for row in 9..=11 {
+        for col in 9..=11 {
+            universe.set_cell(row, col, Cell::Dead).unwrap();
+        }
+    }
+    universe.set_cell(9, 9, Cell::Alive).unwrap();
+    universe.set_cell(9, 10, Cell::Alive).unwrap();
+    universe.set_cell(9, 11, Cell::Alive).unwrap();
+    assert!(is_alive_in_render(&universe, 9, 10));
+    assert!(!is_alive_in_render(&universe, 10, 10));
+
+    // Three live neighbors means the center is born on the next tick.
+    universe.tick();
+    assert!(is_alive_in_render(&universe, 10, 10));
+}
+
+#[wasm_bindgen_test]
+fn test_clear_kills_everything_and_keeps_dimensions() {
+    let mut universe = Universe::new();
+    let (width, height) = (universe.width(), universe.height());
+
+    universe.clear();
+    assert_eq!(universe.width(), width);
+    assert_eq!(universe.height(), height);
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_population_matches_new_seeding() {
+    let universe = Universe::new();
+    let size = universe.width() * universe.height();
+    let expected = (0..size).filter(|i| i % 2 == 0 || i % 7 == 0).count() as u32;
+
+    assert_eq!(universe.population(), expected);
+}
+
+#[wasm_bindgen_test]
+fn test_new_with_size_dimensions() {
+    let universe = Universe::new_with_size(10, 20);
+
+    assert_eq!(universe.width(), 10);
+    assert_eq!(universe.height(), 20);
+    assert_eq!(universe.render().lines().count(), 20);
+}
+
+#[wasm_bindgen_test]
+#[should_panic]
+fn test_new_with_size_rejects_zero() {
+    Universe::new_with_size(0, 5);
+}
+
+#[wasm_bindgen_test]
+fn test_set_width_reallocates_dead_cells() {
+    let mut universe = Universe::new_with_size(8, 6);
+
+    universe.set_width(12);
+    assert_eq!(universe.width(), 12);
+    assert_eq!(rendered_cell_count(&universe), 12 * 6);
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_set_height_reallocates_dead_cells() {
+    let mut universe = Universe::new_with_size(8, 6);
+
+    universe.set_height(3);
+    assert_eq!(universe.height(), 3);
+    assert_eq!(rendered_cell_count(&universe), 8 * 3);
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_resize_preserves_live_cells() {
+    let mut universe = Universe::new_with_size(8, 8);
+    universe.clear();
+    universe.set_cell(5, 5, Cell::Alive).unwrap();
+
+    universe.resize(16, 12);
+    assert_eq!(universe.width(), 16);
+    assert_eq!(universe.height(), 12);
+    assert!(is_alive_in_render(&universe, 5, 5));
+    assert_eq!(universe.population(), 1);
+
+    // Shrinking past the cell drops it.
+    universe.resize(4, 4);
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_generation_counts_ticks() {
+    let mut universe = Universe::new_with_size(8, 8);
+    assert_eq!(universe.generation(), 0);
+
+    universe.tick();
+    universe.tick();
+    universe.tick();
+    assert_eq!(universe.generation(), 3);
+
+    universe.clear();
+    assert_eq!(universe.generation(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_toroidal_blinker_wraps_around_edge() {
+    let mut universe = edge_blinker();
+    assert_eq!(universe.topology(), Topology::Toroidal);
+
+    universe.tick();
+    assert_eq!(universe.population(), 3);
+    assert!(is_alive_in_render(&universe, 2, 4));
+    assert!(is_alive_in_render(&universe, 2, 0));
+    assert!(is_alive_in_render(&universe, 2, 1));
+}
+
+#[wasm_bindgen_test]
+fn test_bounded_blinker_loses_cells_off_edge() {
+    let mut universe = edge_blinker();
+    universe.set_topology(Topology::Bounded);
+
+    universe.tick();
+    assert_eq!(universe.population(), 2);
+    assert!(!is_alive_in_render(&universe, 2, 4));
+    assert!(is_alive_in_render(&universe, 2, 0));
+    assert!(is_alive_in_render(&universe, 2, 1));
+}
+
+#[wasm_bindgen_test]
+fn test_default_rule_is_conway() {
+    let mut universe = Universe::new_with_size(5, 5);
+    universe.clear();
+    stamp(&mut universe, 1, 2, &["O", "O", "O"]);
+
+    universe.tick();
+    assert!(matches_pattern(&universe, 1, 1, &["...", "OOO", "..."]));
+
+    universe.tick();
+    assert!(matches_pattern(&universe, 1, 1, &[".O.", ".O.", ".O."]));
+}
+
+#[wasm_bindgen_test]
+fn test_highlife_replicator_copies_itself() {
+    let mut universe = Universe::new_with_size(24, 24);
+    universe.clear();
+    universe.set_rule(&[3, 6], &[2, 3]);
+    stamp(&mut universe, 10, 10, &REPLICATOR);
+
+    for _ in 0..12 {
+        universe.tick();
+    }
+
+    // After 12 generations two copies sit diagonally on either side.
+    assert_eq!(universe.population(), 24);
+    assert!(matches_pattern(&universe, 8, 8, &REPLICATOR));
+    assert!(matches_pattern(&universe, 12, 12, &REPLICATOR));
+}
+
+#[wasm_bindgen_test]
+fn test_replicator_does_not_replicate_under_conway() {
+    let mut universe = Universe::new_with_size(24, 24);
+    universe.clear();
+    stamp(&mut universe, 10, 10, &REPLICATOR);
+
+    for _ in 0..12 {
+        universe.tick();
+    }
+
+    assert!(!matches_pattern(&universe, 8, 8, &REPLICATOR));
+}
+
+#[wasm_bindgen_test]
+fn test_set_rule_string_conway() {
+    let mut universe = Universe::new_with_size(5, 5);
+    universe.set_rule(&[3, 6], &[2, 3]);
+    universe.set_rule_string("B3/S23").unwrap();
+    universe.clear();
+    stamp(&mut universe, 1, 1, &["O.O", "O..", "OOO"]);
+
+    // The center has six neighbors: born under HighLife, not under Conway.
+    universe.tick();
+    assert!(!is_alive_in_render(&universe, 2, 2));
+}
+
+#[wasm_bindgen_test]
+fn test_set_rule_string_highlife() {
+    let mut universe = Universe::new_with_size(5, 5);
+    universe.set_rule_string("  b36/s23 ").unwrap();
+    universe.clear();
+    stamp(&mut universe, 1, 1, &["O.O", "O..", "OOO"]);
+
+    universe.tick();
+    assert!(is_alive_in_render(&universe, 2, 2));
+}
+
+#[wasm_bindgen_test]
+fn test_set_rule_string_rejects_malformed() {
+    let mut universe = Universe::new_with_size(5, 5);
+
+    assert!(universe.set_rule_string("B9/Sx").is_err());
+    assert!(universe.set_rule_string("B33/S23").is_err());
+    assert!(universe.set_rule_string("23/3").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_tick_reuses_buffer_correctly() {
+    let mut universe = Universe::new_with_size(6, 6);
+    universe.clear();
+    stamp(&mut universe, 1, 2, &["O", "O", "O"]);
+
+    // Several ticks in a row exercise the swapped buffers.
+    for _ in 0..5 {
+        universe.tick();
+        assert!(matches_pattern(&universe, 1, 1, &["...", "OOO", "..."]));
+        universe.tick();
+        assert!(matches_pattern(&universe, 1, 1, &[".O.", ".O.", ".O."]));
+    }
+    assert_eq!(universe.population(), 3);
+
+    // A resize between ticks must not leave a stale scratch buffer behind.
+    universe.resize(8, 8);
+    universe.tick();
+    assert!(matches_pattern(&universe, 1, 1, &["...", "OOO", "..."]));
+}
+
+#[wasm_bindgen_test]
+fn test_bitset_tick_matches_byte_reference() {
+    let mut universe = Universe::new_with_size(13, 7);
+    let mut expected = render_bytes(&universe);
+
+    for _ in 0..10 {
+        universe.tick();
+        expected = reference_tick(13, 7, &expected);
+        assert_eq!(render_bytes(&universe), expected);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_tick_after_shrinking_resize() {
+    let mut universe = Universe::new_with_size(9, 9);
+    universe.tick();
+    universe.resize(5, 4);
+    universe.tick();
+
+    assert_eq!(rendered_cell_count(&universe), 20);
+    assert!(universe.population() <= 20);
+}
+
+#[wasm_bindgen_test]
+fn test_changed_cells_after_blinker_tick() {
+    let mut universe = Universe::new_with_size(5, 5);
+    universe.clear();
+    stamp(&mut universe, 1, 2, &["O", "O", "O"]);
+    assert!(universe.changed_cells().is_empty());
+
+    // Vertical -> horizontal: the ends die and the sides are born, the
+    // center survives.
+    universe.tick();
+    let mut changed = universe.changed_cells();
+    changed.sort();
+    // (1, 2), (2, 1), (2, 3) and (3, 2) on a board five cells wide.
+    assert_eq!(changed, vec![7, 11, 13, 17]);
+}
+
+#[wasm_bindgen_test]
+fn test_insert_glider_moves_diagonally() {
+    let mut universe = Universe::new_with_size(10, 10);
+    universe.clear();
+    universe.insert_glider(2, 3);
+    assert_eq!(universe.population(), 5);
+    assert!(matches_pattern(&universe, 2, 3, &GLIDER));
+
+    for _ in 0..4 {
+        universe.tick();
+    }
+    assert_eq!(universe.population(), 5);
+    assert!(matches_pattern(&universe, 3, 4, &GLIDER));
+}
+
+#[wasm_bindgen_test]
+fn test_insert_glider_wraps_near_edges() {
+    let mut universe = Universe::new_with_size(10, 10);
+    universe.clear();
+    universe.insert_glider(9, 9);
+
+    assert_eq!(universe.population(), 5);
+    assert!(is_alive_in_render(&universe, 9, 0));
+    assert!(is_alive_in_render(&universe, 0, 1));
+    assert!(is_alive_in_render(&universe, 1, 9));
+}
+
+#[wasm_bindgen_test]
+fn test_insert_pattern_population() {
+    let cases = [
+        (Pattern::Glider, 5),
+        (Pattern::Blinker, 3),
+        (Pattern::Toad, 6),
+        (Pattern::Beacon, 6),
+        (Pattern::Pulsar, 48),
+        (Pattern::GosperGliderGun, 36),
+    ];
+
+    for &(pattern, expected) in cases.iter() {
+        let mut universe = Universe::new_with_size(40, 40);
+        universe.clear();
+        universe.insert_pattern(pattern, 5, 2);
+        assert_eq!(universe.population(), expected, "{:?}", pattern);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_inserted_blinker_has_period_two() {
+    let mut universe = Universe::new_with_size(8, 8);
+    universe.clear();
+    universe.insert_pattern(Pattern::Blinker, 3, 2);
+    let original = universe.render();
+
+    universe.tick();
+    assert_ne!(universe.render(), original);
+    universe.tick();
+    assert_eq!(universe.render(), original);
+}
+
+#[wasm_bindgen_test]
+fn test_load_rle_glider() {
+    let mut universe = Universe::new_with_size(8, 8);
+    universe
+        .load_rle("#N Glider\n#C A comment\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!")
+        .unwrap();
+
+    assert_eq!(universe.population(), 5);
+    assert!(matches_pattern(&universe, 0, 0, &GLIDER));
+}
+
+#[wasm_bindgen_test]
+fn test_load_rle_multi_digit_runs_without_terminator() {
+    let mut universe = Universe::new_with_size(8, 8);
+    universe.load_rle("x = 12, y = 3\n12o$\n2$11bo").unwrap();
+
+    // The board grows to fit the 12-wide pattern; the missing `!` is fine.
+    assert_eq!(universe.width(), 12);
+    assert_eq!(universe.height(), 8);
+    assert_eq!(universe.population(), 13);
+    assert!(is_alive_in_render(&universe, 0, 11));
+    assert!(is_alive_in_render(&universe, 3, 11));
+}
+
+#[wasm_bindgen_test]
+fn test_load_rle_applies_header_rule() {
+    let mut universe = Universe::new_with_size(5, 5);
+    universe
+        .load_rle("x = 3, y = 3, rule = B36/S23\n3o$o$obo!")
+        .unwrap();
+
+    // The center has six neighbors, so it is only born under HighLife.
+    universe.tick();
+    assert!(is_alive_in_render(&universe, 1, 1));
+}
+
+#[wasm_bindgen_test]
+fn test_load_rle_rejects_malformed_body() {
+    let mut universe = Universe::new_with_size(5, 5);
+    assert!(universe.load_rle("x = 3, y = 3\nbo$2bz!").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_to_rle_glider() {
+    let mut universe = Universe::new_with_size(8, 8);
+    universe.clear();
+    universe.insert_glider(2, 3);
+
+    assert_eq!(
+        universe.to_rle(),
+        "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_to_rle_collapses_empty_rows() {
+    let mut universe = Universe::new_with_size(8, 8);
+    universe.clear();
+    universe.set_cell(1, 1, Cell::Alive).unwrap();
+    universe.set_cell(4, 3, Cell::Alive).unwrap();
+
+    // Rows 2 and 3 are empty, so the row ends merge into `3$`.
+    assert_eq!(universe.to_rle(), "x = 3, y = 4, rule = B3/S23\no3$2bo!\n");
+}
+
+#[wasm_bindgen_test]
+fn test_rle_round_trip() {
+    let mut universe = Universe::new_with_size(40, 20);
+    universe.clear();
+    universe.insert_pattern(Pattern::GosperGliderGun, 0, 0);
+    universe.insert_pattern(Pattern::Pulsar, 7, 20);
+
+    let mut loaded = Universe::new_with_size(40, 20);
+    loaded.load_rle(&universe.to_rle()).unwrap();
+    assert_eq!(loaded.render(), universe.render());
+}
+
+#[wasm_bindgen_test]
+fn test_plaintext_glider_round_trip() {
+    let text = "!Name: Glider\n!\n.O.\n..O\nOOO\n";
+    let mut universe = Universe::new_with_size(8, 8);
+    universe.load_plaintext(text);
+
+    assert_eq!(universe.population(), 5);
+    assert!(matches_pattern(&universe, 0, 0, &GLIDER));
+    assert_eq!(universe.to_plaintext(), ".O.\n..O\nOOO\n");
+}
+
+#[wasm_bindgen_test]
+fn test_to_plaintext_trims_to_bounding_box() {
+    let mut universe = Universe::new_with_size(10, 10);
+    universe.clear();
+    universe.set_cell(3, 4, Cell::Alive).unwrap();
+    universe.set_cell(5, 7, Cell::Alive).unwrap();
+
+    assert_eq!(universe.to_plaintext(), "O...\n....\n...O\n");
+}
+
+#[wasm_bindgen_test]
+fn test_load_plaintext_treats_other_characters_as_dead() {
+    let mut universe = Universe::new_with_size(4, 4);
+    universe.load_plaintext("O*o\n.xO\n");
+
+    assert_eq!(universe.population(), 2);
+    assert!(is_alive_in_render(&universe, 0, 0));
+    assert!(is_alive_in_render(&universe, 1, 2));
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test]
+fn test_json_round_trip() {
+    let mut universe = Universe::new_with_size(7, 5);
+    universe.tick();
+    universe.tick();
+
+    let json = universe.to_json();
+    assert!(!json.contains("Alive"));
+
+    let restored = Universe::from_json(&json).unwrap();
+    assert_eq!(restored.width(), 7);
+    assert_eq!(restored.height(), 5);
+    assert_eq!(restored.generation(), 2);
+    assert_eq!(restored.render(), universe.render());
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test]
+fn test_from_json_rejects_malformed_cell_count() {
+    let json = r#"{"width":2,"height":2,"generation":0,"cells":[0,1,0]}"#;
+    assert!(Universe::from_json(json).is_err());
+}
+
+#[wasm_bindgen_test]
+#[cfg(feature = "serde")]
+fn test_from_json_rejects_overflowing_dimensions() {
+    let json = r#"{"width":65536,"height":65536,"generation":0,"cells":[]}"#;
+    assert_eq!(
+        Universe::from_json(json).err(),
+        Some(GameError::InvalidDimensions {
+            width: 65536,
+            height: 65536
+        })
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_tick_n_matches_repeated_tick() {
+    let mut universe = Universe::new_with_size(10, 10);
+    universe.clear();
+    universe.insert_glider(2, 3);
+    let mut stepped = Universe::new_with_size(10, 10);
+    stepped.clear();
+    stepped.insert_glider(2, 3);
+
+    universe.tick_n(4);
+    for _ in 0..4 {
+        stepped.tick();
+    }
+
+    assert!(matches_pattern(&universe, 3, 4, &GLIDER));
+    assert_eq!(universe.render(), stepped.render());
+    assert_eq!(universe.generation(), 4);
+}
+
+#[wasm_bindgen_test]
+fn test_randomize_is_deterministic() {
+    let mut first = Universe::new_with_size(32, 32);
+    let mut second = Universe::new_with_size(32, 32);
+    first.randomize(42, 0.3);
+    second.randomize(42, 0.3);
+
+    assert_eq!(first.render(), second.render());
+    assert_eq!(first.population(), 325);
+
+    second.randomize(43, 0.3);
+    assert_ne!(first.render(), second.render());
+}
+
+#[wasm_bindgen_test]
+fn test_randomize_density_extremes() {
+    let mut universe = Universe::new_with_size(16, 16);
+
+    universe.randomize(7, 0.0);
+    assert_eq!(universe.population(), 0);
+    universe.randomize(7, 1.0);
+    assert_eq!(universe.population(), 16 * 16);
+}
+
+#[wasm_bindgen_test]
+fn test_reset_restores_initial_seeding() {
+    let mut universe = Universe::new_with_size(12, 9);
+    universe.randomize(1, 0.5);
+    universe.tick_n(3);
+
+    universe.reset();
+    assert_eq!(universe.generation(), 0);
+    assert_eq!(universe.render(), Universe::new_with_size(12, 9).render());
+}
+
+#[wasm_bindgen_test]
+fn test_live_bounds() {
+    let mut universe = Universe::new_with_size(10, 10);
+    universe.clear();
+    assert_eq!(universe.live_bounds(), None);
+
+    universe.set_cell(2, 7, Cell::Alive).unwrap();
+    universe.set_cell(6, 3, Cell::Alive).unwrap();
+    assert_eq!(
+        universe.live_bounds(),
+        Some(Bounds {
+            min_row: 2,
+            min_col: 3,
+            max_row: 6,
+            max_col: 7,
+        })
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_block_is_stable() {
+    let universe = block_universe();
+
+    assert!(universe.is_stable());
+    assert_eq!(universe.is_periodic(5), Some(1));
+}
+
+#[wasm_bindgen_test]
+fn test_blinker_has_period_two() {
+    let mut universe = Universe::new_with_size(6, 6);
+    universe.clear();
+    universe.insert_pattern(Pattern::Blinker, 2, 1);
+
+    assert!(!universe.is_stable());
+    assert_eq!(universe.is_periodic(5), Some(2));
+    assert_eq!(universe.is_periodic(1), None);
+}
+
+#[wasm_bindgen_test]
+fn test_glider_is_not_periodic_on_large_board() {
+    let mut universe = Universe::new_with_size(20, 20);
+    universe.clear();
+    universe.insert_glider(2, 2);
+
+    assert_eq!(universe.is_periodic(8), None);
+}
+
+#[wasm_bindgen_test]
+fn test_next_generation_leaves_original_untouched() {
+    let mut universe = Universe::new_with_size(10, 10);
+    universe.set_topology(Topology::Bounded);
+    universe.set_rule_string("B36/S23").unwrap();
+    let before = universe.render();
+
+    let next = universe.next_generation();
+    assert_eq!(universe.render(), before);
+    assert_eq!(universe.generation(), 0);
+
+    universe.tick();
+    assert_eq!(next.render(), universe.render());
+    assert_eq!(next.generation(), 1);
+    assert_eq!(next.topology(), Topology::Bounded);
+}
+
+#[wasm_bindgen_test]
+fn test_get_cell_reads_set_cell() {
+    let mut universe = Universe::new_with_size(6, 6);
+    universe.clear();
+    universe.set_cell(4, 1, Cell::Alive).unwrap();
+
+    assert_eq!(universe.get_cell(4, 1), Cell::Alive);
+    assert_eq!(universe.get_cell(1, 4), Cell::Dead);
+    assert_eq!(universe.get_cell(10, 7), Cell::Alive);
+}
+
+#[wasm_bindgen_test]
+fn test_block_ages_grow_each_tick() {
+    let mut universe = block_universe();
+    assert_eq!(universe.ages_len(), 36);
+    assert_eq!(universe.age(2, 2), 0);
+
+    universe.tick_n(3);
+    for &(row, col) in [(2, 2), (2, 3), (3, 2), (3, 3)].iter() {
+        assert_eq!(universe.age(row, col), 3);
+    }
+    assert_eq!(universe.age(1, 1), 0);
+    assert_eq!(universe.age(4, 4), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_newly_born_cells_start_at_age_one() {
+    let mut universe = Universe::new_with_size(6, 6);
+    universe.clear();
+    universe.insert_pattern(Pattern::Blinker, 2, 1);
+
+    universe.tick();
+    assert_eq!(universe.age(1, 2), 1);
+    assert_eq!(universe.age(2, 2), 1);
+    assert_eq!(universe.age(2, 1), 0);
+
+    universe.tick();
+    assert_eq!(universe.age(2, 2), 2);
+    assert_eq!(universe.age(2, 1), 1);
+}
+
+#[wasm_bindgen_test]
+fn test_tick_matches_reference_on_random_board() {
+    // Runs the parallel engine when built with the `parallel` feature and the
+    // serial one otherwise; both must agree with the byte-per-cell reference.
+    let mut universe = Universe::new_with_size(67, 41);
+    universe.randomize(2024, 0.35);
+    let mut expected = render_bytes(&universe);
+
+    for _ in 0..20 {
+        universe.tick();
+        expected = reference_tick(67, 41, &expected);
+        assert_eq!(render_bytes(&universe), expected);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_glider_crossing_edges_matches_reference() {
+    // The glider repeatedly wraps across the corner, exercising both the
+    // interior fast path and the wrapping border path.
+    let mut universe = Universe::new_with_size(7, 6);
+    universe.clear();
+    universe.insert_glider(3, 4);
+    let mut expected = render_bytes(&universe);
+
+    for _ in 0..48 {
+        universe.tick();
+        expected = reference_tick(7, 6, &expected);
+        assert_eq!(render_bytes(&universe), expected);
+    }
+    assert_eq!(universe.population(), 5);
+}
+
+#[wasm_bindgen_test]
+fn test_empty_universe() {
+    let universe = Universe::empty(9, 4);
+
+    assert_eq!(universe.width(), 9);
+    assert_eq!(universe.height(), 4);
+    assert_eq!(universe.population(), 0);
+    assert_eq!(universe.generation(), 0);
+}
+
+#[wasm_bindgen_test]
+#[should_panic]
+fn test_empty_rejects_zero() {
+    Universe::empty(3, 0);
+}
+
+#[wasm_bindgen_test]
+fn test_single_cell_in_1x1_grid_has_no_neighbors() {
+    // Under B/S0 a live cell survives only with exactly zero neighbors.
+    let mut universe = Universe::empty(1, 1);
+    universe.set_rule_string("B/S0").unwrap();
+    universe.set_cell(0, 0, Cell::Alive).unwrap();
+
+    universe.tick();
+    assert_eq!(universe.get_cell(0, 0), Cell::Alive);
+}
+
+#[wasm_bindgen_test]
+fn test_one_wide_universe_ticks() {
+    let mut universe = Universe::empty(1, 5);
+    universe.set_cell(1, 0, Cell::Alive).unwrap();
+    universe.set_cell(2, 0, Cell::Alive).unwrap();
+    universe.set_cell(3, 0, Cell::Alive).unwrap();
+
+    // The middle cell has exactly two neighbors (above and below) and survives;
+    // the ends have one each and die. Nothing is born with only two.
+    universe.tick();
+    assert_eq!(universe.population(), 1);
+    assert_eq!(universe.get_cell(2, 0), Cell::Alive);
+}
+
+#[wasm_bindgen_test]
+fn test_two_wide_neighbors_count_once() {
+    // On a 2x2 torus each cell has exactly three distinct neighbors, so a full
+    // board survives under Conway rather than dying of overcrowding.
+    let mut universe = Universe::empty(2, 2);
+    stamp(&mut universe, 0, 0, &["OO", "OO"]);
+
+    universe.tick();
+    assert_eq!(universe.population(), 4);
+}
+
+#[wasm_bindgen_test]
+#[should_panic]
+fn test_resize_rejects_zero() {
+    Universe::empty(3, 3).resize(0, 3);
+}
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "at most")]
+fn test_new_with_size_rejects_overflowing_dimensions() {
+    Universe::new_with_size(65536, 65536);
+}
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "at most")]
+fn test_resize_rejects_overflowing_dimensions() {
+    Universe::empty(3, 3).resize(100_000, 100_000);
+}
+
+#[wasm_bindgen_test]
+fn test_set_cells_from_flattened_pairs() {
+    let mut universe = Universe::empty(6, 6);
+    universe.set_cells(&[1, 2, 3, 4]);
+
+    assert_eq!(universe.population(), 2);
+    assert_eq!(universe.get_cell(1, 2), Cell::Alive);
+    assert_eq!(universe.get_cell(3, 4), Cell::Alive);
+}
+
+#[wasm_bindgen_test]
+fn test_live_cells_in_row_major_order() {
+    let mut universe = Universe::empty(6, 6);
+    universe.set_cells(&[4, 1, 0, 5, 4, 0]);
+
+    assert_eq!(universe.live_cells(), vec![0, 5, 4, 0, 4, 1]);
+    assert_eq!(
+        universe.live_cells_iter().collect::<Vec<_>>(),
+        vec![(0, 5), (4, 0), (4, 1)]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_render_rgba_layout() {
+    let mut universe = Universe::empty(2, 2);
+    universe.set_cell(0, 1, Cell::Alive).unwrap();
+
+    let pixels = universe.render_rgba(0x11223344, 0xAABBCCDD);
+    assert_eq!(
+        pixels,
+        vec![
+            0xAA, 0xBB, 0xCC, 0xDD, // (0, 0) dead
+            0x11, 0x22, 0x33, 0x44, // (0, 1) alive
+            0xAA, 0xBB, 0xCC, 0xDD, // (1, 0) dead
+            0xAA, 0xBB, 0xCC, 0xDD, // (1, 1) dead
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_render_with_custom_glyphs() {
+    let mut universe = Universe::empty(4, 3);
+    universe.set_cells(&[0, 1, 1, 2, 2, 0]);
+
+    assert_eq!(universe.render_with('#', '.'), ".#..\n..#.\n#...\n");
+    assert_eq!(universe.render(), universe.render_with('◼', '◻'));
+}
+
+#[wasm_bindgen_test]
+fn test_render_ansi_escape_sequences() {
+    let mut universe = Universe::empty(3, 2);
+    universe.set_cells(&[0, 1]);
+
+    let ansi = universe.render_ansi();
+    let lines: Vec<&str> = ansi.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], "\x1b[49m \x1b[42m \x1b[49m \x1b[0m");
+    assert_eq!(lines[1], "\x1b[49m   \x1b[0m");
+}
+
+#[wasm_bindgen_test]
+fn test_universe_equality() {
+    let first = Universe::new_with_size(8, 8);
+    let mut second = Universe::new_with_size(8, 8);
+    assert_eq!(first, second);
+
+    second.tick();
+    assert_ne!(first, second);
+
+    // Same cells but different dimensions are not equal.
+    assert_ne!(Universe::empty(4, 2), Universe::empty(2, 4));
+}
+
+#[wasm_bindgen_test]
+fn test_equality_ignores_generation() {
+    let mut universe = Universe::empty(6, 6);
+    universe.insert_pattern(Pattern::Blinker, 2, 1);
+    let start = universe.clone();
+
+    universe.tick_n(2);
+    assert_eq!(universe, start);
+}
+
+#[wasm_bindgen_test]
+fn test_state_hash() {
+    let mut universe = Universe::new_with_size(16, 16);
+    let copy = universe.clone();
+    assert_eq!(universe.state_hash(), copy.state_hash());
+    assert_eq!(
+        universe.state_hash(),
+        Universe::new_with_size(16, 16).state_hash()
+    );
+
+    universe.tick();
+    assert_ne!(universe, copy);
+    assert_ne!(universe.state_hash(), copy.state_hash());
+
+    // Same (empty) cells with transposed dimensions hash differently.
+    assert_ne!(
+        Universe::empty(4, 2).state_hash(),
+        Universe::empty(2, 4).state_hash()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_from_cells() {
+    let universe = Universe::from_cells(3, 3, &[0, 1, 0, 0, 0, 7, 1, 1, 1]).unwrap();
+
+    assert_eq!(universe.width(), 3);
+    assert_eq!(universe.height(), 3);
+    assert_eq!(universe.render_with('O', '.'), ".O.\n..O\nOOO\n");
+}
+
+#[wasm_bindgen_test]
+fn test_from_cells_rejects_malformed_length() {
+    assert!(Universe::from_cells(3, 3, &[0, 1, 0]).is_err());
+    assert!(Universe::from_cells(0, 3, &[]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_rotate_cw_glider() {
+    let mut universe = Universe::empty(6, 4);
+    universe.insert_glider(0, 0);
+
+    universe.rotate_cw();
+    assert_eq!(universe.width(), 4);
+    assert_eq!(universe.height(), 6);
+    assert_eq!(universe.population(), 5);
+    assert!(matches_pattern(&universe, 0, 1, &["O..", "O.O", "OO."]));
+}
+
+#[wasm_bindgen_test]
+fn test_four_rotations_are_identity() {
+    let mut universe = Universe::new_with_size(7, 3);
+    let original = universe.clone();
+
+    for _ in 0..4 {
+        universe.rotate_cw();
+    }
+    assert_eq!(universe, original);
+}
+
+#[wasm_bindgen_test]
+fn test_flips() {
+    let mut universe = Universe::empty(5, 4);
+    universe.insert_glider(0, 0);
+    let original = universe.clone();
+
+    universe.flip_horizontal();
+    assert!(matches_pattern(&universe, 0, 2, &[".O.", "O..", "OOO"]));
+    universe.flip_horizontal();
+    assert_eq!(universe, original);
+
+    universe.flip_vertical();
+    assert!(matches_pattern(&universe, 1, 0, &["OOO", "..O", ".O."]));
+    universe.flip_vertical();
+    assert_eq!(universe, original);
+}
+
+#[wasm_bindgen_test]
+fn test_translate_wraps_around_edges() {
+    let mut universe = Universe::empty(4, 3);
+    universe.set_cell(1, 2, Cell::Alive).unwrap();
+
+    universe.translate(1, 0);
+    assert_eq!(universe.live_cells(), vec![2, 2]);
+
+    // Moving down off the bottom edge wraps to the top row.
+    universe.translate(1, 0);
+    assert_eq!(universe.live_cells(), vec![0, 2]);
+
+    universe.translate(-1, -3);
+    assert_eq!(universe.live_cells(), vec![2, 3]);
+}
+
+#[wasm_bindgen_test]
+fn test_von_neumann_ignores_diagonals() {
+    // Under B4/S a dead cell is born with exactly four live neighbors.
+    let diagonals = ["O.O", "...", "O.O"];
+
+    let mut moore = Universe::empty(5, 5);
+    moore.set_rule_string("B4/S").unwrap();
+    stamp(&mut moore, 1, 1, &diagonals);
+    moore.tick();
+    assert_eq!(moore.get_cell(2, 2), Cell::Alive);
+
+    let mut von_neumann = Universe::empty(5, 5);
+    von_neumann.set_rule_string("B4/S").unwrap();
+    von_neumann.set_neighborhood(Neighborhood::VonNeumann);
+    stamp(&mut von_neumann, 1, 1, &diagonals);
+    von_neumann.tick();
+    assert_eq!(von_neumann.get_cell(2, 2), Cell::Dead);
+}
+
+#[wasm_bindgen_test]
+fn test_von_neumann_counts_orthogonal_ring() {
+    // A full ring is 8 Moore neighbors but only 4 von Neumann ones.
+    let ring = ["OOO", "O.O", "OOO"];
+
+    for &topology in [Topology::Toroidal, Topology::Bounded].iter() {
+        let mut universe = Universe::empty(3, 3);
+        universe.set_topology(topology);
+        universe.set_rule_string("B4/S").unwrap();
+        universe.set_neighborhood(Neighborhood::VonNeumann);
+        stamp(&mut universe, 0, 0, &ring);
+
+        universe.tick();
+        assert_eq!(universe.get_cell(1, 1), Cell::Alive, "{:?}", topology);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_von_neumann_on_wrapped_border() {
+    // The ring wraps around the corner, so (0, 0) goes through the border path.
+    let mut universe = Universe::empty(5, 5);
+    universe.set_rule_string("B4/S").unwrap();
+    universe.set_neighborhood(Neighborhood::VonNeumann);
+    stamp(&mut universe, 4, 4, &["OOO", "O.O", "OOO"]);
+
+    universe.tick();
+    assert_eq!(universe.get_cell(0, 0), Cell::Alive);
+}
+
+#[wasm_bindgen_test]
+fn test_neighbor_radius_two_counts_24() {
+    // Only the center of the 5x5 block sees all 24 cells of its radius-2 square.
+    let mut universe = Universe::empty(9, 9);
+    universe.set_neighbor_radius(2);
+    universe.set_rule(&[], &[24]);
+    stamp(&mut universe, 2, 2, &["OOOOO"; 5]);
+
+    universe.tick();
+    assert_eq!(universe.population(), 1);
+    assert_eq!(universe.get_cell(4, 4), Cell::Alive);
+}
+
+#[wasm_bindgen_test]
+fn test_neighbor_radius_wraps_on_small_torus() {
+    // On a full 5x5 torus every cell goes through the border path and still
+    // finds 24 distinct live neighbors.
+    let mut universe = Universe::empty(5, 5);
+    universe.set_neighbor_radius(2);
+    universe.set_rule(&[], &[24]);
+    stamp(&mut universe, 0, 0, &["OOOOO"; 5]);
+
+    universe.tick();
+    assert_eq!(universe.population(), 25);
+    assert_eq!(universe.neighbor_radius(), 2);
+}
+
+#[wasm_bindgen_test]
+fn test_undo_reverts_tick() {
+    let mut universe = Universe::new_with_size(16, 16);
+    let before = universe.clone();
+
+    universe.tick();
+    assert!(universe.undo());
+    assert_eq!(universe, before);
+    assert_eq!(universe.generation(), 0);
+    assert!(!universe.undo());
+}
+
+#[wasm_bindgen_test]
+fn test_undo_reverts_edits_in_order() {
+    let mut universe = Universe::empty(8, 8);
+    universe.set_cell(1, 1, Cell::Alive).unwrap();
+    universe.toggle_cell(2, 2);
+    universe.clear();
+
+    assert!(universe.undo());
+    assert_eq!(universe.population(), 2);
+    assert!(universe.undo());
+    assert_eq!(universe.get_cell(2, 2), Cell::Dead);
+    assert!(universe.undo());
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_undo_history_is_bounded() {
+    let mut universe = Universe::empty(8, 8);
+    universe.set_history_depth(3);
+    for col in 0..5 {
+        universe.set_cell(0, col, Cell::Alive).unwrap();
+    }
+
+    let mut undone = 0;
+    while universe.undo() {
+        undone += 1;
+    }
+    assert_eq!(undone, 3);
+    assert_eq!(universe.population(), 2);
+}
+
+#[wasm_bindgen_test]
+fn test_restore_checkpoint_after_ticks() {
+    let mut universe = Universe::new_with_size(16, 16);
+    let saved = universe.clone();
+    let handle = universe.save_checkpoint();
+
+    universe.tick_n(5);
+    assert!(universe.restore_checkpoint(handle));
+    assert_eq!(universe, saved);
+    assert_eq!(universe.generation(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_restore_checkpoint_rejects_stale_handles() {
+    let mut universe = Universe::new_with_size(16, 16);
+    let handle = universe.save_checkpoint();
+
+    assert!(!universe.restore_checkpoint(handle + 1));
+    universe.resize(20, 20);
+    assert!(!universe.restore_checkpoint(handle));
+    assert_eq!(universe.width(), 20);
+}
+
+#[wasm_bindgen_test]
+fn test_population_history_records_each_tick() {
+    // A blinker keeps three cells in both phases, while a beacon's population
+    // swings between 6 and 8.
+    let mut blinker = Universe::empty(5, 5);
+    blinker.insert_pattern(Pattern::Blinker, 1, 1);
+    blinker.tick_n(3);
+    assert_eq!(blinker.population_history(), vec![3, 3, 3]);
+
+    let mut beacon = Universe::empty(6, 6);
+    beacon.insert_pattern(Pattern::Beacon, 1, 1);
+    beacon.tick_n(3);
+    assert_eq!(beacon.population_history(), vec![8, 6, 8]);
+
+    beacon.clear_history();
+    assert!(beacon.population_history().is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_population_history_limit_and_clear() {
+    let mut universe = Universe::new_with_size(16, 16);
+    universe.set_population_history_limit(2);
+    universe.tick_n(5);
+    let history = universe.population_history();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1], universe.population());
+
+    universe.clear();
+    assert!(universe.population_history().is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_centroid_averages_live_cells() {
+    let mut universe = Universe::empty(8, 8);
+    assert_eq!(universe.centroid(), None);
+
+    universe.set_cell(0, 0, Cell::Alive).unwrap();
+    universe.set_cell(2, 2, Cell::Alive).unwrap();
+    assert_eq!(universe.centroid(), Some(Centroid { row: 1.0, col: 1.0 }));
+}
+
+#[wasm_bindgen_test]
+fn test_brians_brain_spaceship() {
+    // Brian's Brain (B2/S, 3 states): two live cells trailed by two dying
+    // ones move one row up every tick, so on an 8-row torus the board comes
+    // back after 8 ticks.
+    let mut universe = Universe::empty(8, 8);
+    universe.set_rule_string("B2/S").unwrap();
+    universe.set_states(3);
+    universe.set_cell_state(4, 3, 2);
+    universe.set_cell_state(4, 4, 2);
+    universe.set_cell_state(5, 3, 1);
+    universe.set_cell_state(5, 4, 1);
+    let states =
+        |u: &Universe| -> Vec<u8> { (0..64).map(|i| u.cell_state(i / 8, i % 8)).collect() };
+    let start = states(&universe);
+
+    universe.tick();
+    assert_eq!(universe.population(), 2);
+    assert_eq!(universe.cell_state(3, 3), 2);
+    assert_eq!(universe.cell_state(4, 4), 1);
+    assert_eq!(universe.cell_state(5, 4), 0);
+
+    universe.tick_n(7);
+    assert_eq!(states(&universe), start);
+}
+
+#[wasm_bindgen_test]
+fn test_dying_cells_decay_before_rebirth() {
+    // A lone cell with no survival counts spends 3 ticks dying under a
+    // 5-state rule before it can be born again.
+    let mut universe = Universe::empty(5, 5);
+    universe.set_rule_string("B1/S").unwrap();
+    universe.set_states(5);
+    universe.set_cell(2, 2, Cell::Alive).unwrap();
+
+    universe.tick();
+    assert_eq!(universe.cell_state(2, 2), 3);
+    assert_eq!(universe.get_cell(2, 2), Cell::Dead);
+    universe.tick();
+    assert_eq!(universe.cell_state(2, 2), 2);
+}
+
+#[wasm_bindgen_test]
+fn test_simulate_concatenates_frames() {
+    let mut universe = Universe::empty(5, 5);
+    universe.insert_pattern(Pattern::Blinker, 2, 1);
+    let frame =
+        |cells: [usize; 3]| -> Vec<u8> { (0..25).map(|i| cells.contains(&i) as u8).collect() };
+    let vertical = frame([7, 12, 17]);
+    let horizontal = frame([11, 12, 13]);
+
+    let frames = universe.simulate(3);
+    assert_eq!(frames.len(), 3 * 25);
+    assert_eq!(frames, [vertical.clone(), horizontal, vertical].concat());
+    assert_eq!(universe.generation(), 3);
+}
+
+#[wasm_bindgen_test]
+fn test_stats_summarizes_board() {
+    let mut universe = Universe::empty(8, 8);
+    universe.insert_pattern(Pattern::Blinker, 2, 3);
+    universe.tick();
+
+    assert_eq!(
+        universe.stats(),
+        Stats {
+            population: 3,
+            generation: 1,
+            bounds: Some(Bounds {
+                min_row: 1,
+                min_col: 4,
+                max_row: 3,
+                max_col: 4,
+            }),
+            stable: false,
+        }
+    );
+
+    let empty = Universe::empty(4, 4).stats();
+    assert_eq!(empty.bounds, None);
+    assert!(empty.stable);
+}
+
+#[wasm_bindgen_test]
+fn test_neighbor_count_matches_manual_count() {
+    let mut universe = Universe::new_with_size(9, 9);
+    universe.set_topology(Topology::Bounded);
+    for row in 0..9 {
+        for col in 0..9 {
+            let mut expected = 0;
+            for r in row.max(1) - 1..=(row + 1).min(8) {
+                for c in col.max(1) - 1..=(col + 1).min(8) {
+                    if (r, c) != (row, col) && is_alive(&universe, r, c) {
+                        expected += 1;
+                    }
+                }
+            }
+            assert_eq!(
+                universe.neighbor_count(row, col),
+                expected,
+                "({}, {})",
+                row,
+                col
+            );
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_duplicate_is_independent() {
+    let original = Universe::new_with_size(16, 16);
+    let snapshot = original.render();
+    let mut copy = original.duplicate();
+
+    copy.tick_n(3);
+    copy.set_rule_string("B36/S23").unwrap();
+    assert_eq!(original.render(), snapshot);
+    assert_eq!(original.generation(), 0);
+    assert_ne!(copy, original);
+}
+
+#[wasm_bindgen_test]
+fn test_fill_rect_sets_only_the_rectangle() {
+    let mut universe = Universe::empty(8, 8);
+    universe.fill_rect(2, 3, 2, 3, Cell::Alive);
+
+    assert_eq!(universe.population(), 6);
+    assert_eq!(
+        universe.live_cells(),
+        vec![2, 3, 2, 4, 3, 3, 3, 4, 4, 3, 4, 4]
+    );
+    assert!(!is_alive(&universe, 1, 3));
+    assert!(!is_alive(&universe, 2, 5));
+    assert!(!is_alive(&universe, 5, 3));
+
+    universe.fill_rect(6, 6, 10, 10, Cell::Alive);
+    assert_eq!(universe.population(), 10);
+    universe.fill_rect(0, 0, 8, 8, Cell::Dead);
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_glider_gun_emits_a_glider() {
+    // The gun has period 30 and leaves one 5-cell glider behind each cycle.
+    let mut universe = Universe::empty(40, 40);
+    universe.insert_glider_gun(1, 1);
+    assert_eq!(universe.population(), 36);
+
+    universe.tick_n(30);
+    assert_eq!(universe.population(), 41);
+}
+
+#[wasm_bindgen_test]
+fn test_load_life106_glider() {
+    let mut universe = Universe::empty(8, 8);
+    universe
+        .load_life106("#Life 1.06\n#D glider\n0 -1\n1 0\n-1 1\n0 1\n1 1\n\n")
+        .unwrap();
+
+    assert_eq!(universe.population(), 5);
+    assert!(matches_pattern(&universe, 0, 0, &GLIDER));
+    assert_eq!(universe.width(), 8);
+}
+
+#[wasm_bindgen_test]
+fn test_diff_next_marks_births_and_deaths() {
+    let mut universe = Universe::empty(5, 5);
+    universe.insert_pattern(Pattern::Blinker, 2, 1);
+
+    assert_eq!(universe.diff_next(), ".....\n..+..\n.-#-.\n..+..\n.....\n");
+    assert_eq!(universe.generation(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_cells_len_follows_resize() {
+    let mut universe = Universe::new_with_size(8, 6);
+    assert_eq!(universe.cells_len(), 48);
+
+    universe.resize(5, 3);
+    assert_eq!(
+        universe.cells_len(),
+        (universe.width() * universe.height()) as usize
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_sparse_glider_moves_diagonally() {
+    // Starting at negative coordinates shows the plane has no edges.
+    let mut universe = SparseUniverse::new();
+    universe.insert_pattern(Pattern::Glider, -2, -2);
+    let start = universe.live_cells();
+
+    universe.tick_n(4);
+    let moved: Vec<i64> = start.iter().map(|&coord| coord + 1).collect();
+    assert_eq!(universe.live_cells(), moved);
+    assert_eq!(universe.population(), 5);
+    assert_eq!(universe.generation(), 4);
+}
+
+#[wasm_bindgen_test]
+fn test_sparse_matches_dense_blinker() {
+    let mut sparse = SparseUniverse::new();
+    sparse.insert_pattern(Pattern::Blinker, 1_000_000, 1_000_000);
+    sparse.tick();
+
+    assert_eq!(sparse.get_cell(999_999, 1_000_001), Cell::Alive);
+    assert_eq!(sparse.get_cell(1_000_000, 1_000_000), Cell::Dead);
+    assert_eq!(sparse.population(), 3);
+}
+
+#[wasm_bindgen_test]
+fn test_auto_expand_keeps_glider_alive() {
+    // Without expansion the glider would crash into the bounded edge.
+    let mut universe = Universe::empty(6, 6);
+    universe.set_topology(Topology::Bounded);
+    universe.insert_pattern(Pattern::Glider, 2, 2);
+    universe.set_auto_expand(true);
+
+    universe.tick_n(40);
+    assert_eq!(universe.population(), 5);
+    assert!(universe.width() > 6 && universe.height() > 6);
+    assert_eq!(universe.generation(), 40);
+
+    let bounds = universe.live_bounds().unwrap();
+    assert_eq!(bounds.max_row - bounds.min_row, 2);
+    assert_eq!(bounds.max_col - bounds.min_col, 2);
+}
+
+#[wasm_bindgen_test]
+fn test_bytes_round_trip() {
+    let mut universe = Universe::new_with_size(13, 7);
+    universe.randomize(42, 0.4);
+    universe.tick_n(2);
+
+    let bytes = universe.to_bytes();
+    assert_eq!(bytes.len(), 21 + 12);
+    let restored = Universe::from_bytes(&bytes).unwrap();
+    assert_eq!(restored, universe);
+    assert_eq!(restored.generation(), 2);
+}
+
+#[wasm_bindgen_test]
+fn test_from_bytes_rejects_malformed_blobs() {
+    let bytes = Universe::new_with_size(8, 8).to_bytes();
+    assert!(Universe::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Universe::from_bytes(&[b"GOL\0".as_ref(), &[2]].concat()).is_err());
+
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[0] = b'X';
+    assert!(Universe::from_bytes(&wrong_magic).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_symmetry_checks() {
+    // Odd height: the middle row only has to match itself.
+    let mut universe = Universe::empty(4, 5);
+    stamp(
+        &mut universe,
+        0,
+        0,
+        &["O...", ".OO.", "OOOO", ".OO.", "O..."],
+    );
+    assert!(universe.is_symmetric_vertical());
+    assert!(!universe.is_symmetric_horizontal());
+    assert!(!universe.is_symmetric_diagonal());
+
+    universe.set_cell(4, 0, Cell::Dead).unwrap();
+    assert!(!universe.is_symmetric_vertical());
+
+    // Even width, mirrored left to right.
+    let mut universe = Universe::empty(4, 3);
+    stamp(&mut universe, 0, 0, &["O..O", ".OO.", "...."]);
+    assert!(universe.is_symmetric_horizontal());
+    assert!(!universe.is_symmetric_vertical());
+
+    let mut universe = Universe::empty(3, 3);
+    stamp(&mut universe, 0, 0, &["OO.", "O..", "..."]);
+    assert!(universe.is_symmetric_diagonal());
+}
+
+#[wasm_bindgen_test]
+fn test_insert_pattern_transformed_glider() {
+    // A quarter turn clockwise matches rotating the whole board.
+    let mut universe = Universe::empty(6, 6);
+    universe.insert_pattern_transformed(Pattern::Glider, 1, 1, 1, false);
+    assert_eq!(universe.population(), 5);
+    assert!(matches_pattern(&universe, 1, 1, &["O..", "O.O", "OO."]));
+
+    let mut mirrored = Universe::empty(6, 6);
+    mirrored.insert_pattern_transformed(Pattern::Glider, 0, 0, 0, true);
+    assert!(matches_pattern(&mirrored, 0, 0, &[".O.", "O..", "OOO"]));
+
+    // A full turn (or four quarter turns) leaves the pattern as it was.
+    let mut turned = Universe::empty(6, 6);
+    turned.insert_pattern_transformed(Pattern::Glider, 0, 0, 4, false);
+    assert!(matches_pattern(&turned, 0, 0, &GLIDER));
+
+    // The blinker's bounding box turns from 3x1 into 1x3.
+    let mut blinker = Universe::empty(6, 6);
+    blinker.insert_pattern_transformed(Pattern::Blinker, 0, 0, 3, false);
+    assert_eq!(blinker.live_cells(), vec![0, 0, 1, 0, 2, 0]);
+}
+
+#[wasm_bindgen_test]
+fn test_set_cell_out_of_bounds_returns_error() {
+    let mut universe = Universe::empty(5, 4);
+    assert_eq!(
+        universe.set_cell(4, 2, Cell::Alive),
+        Err(GameError::OutOfBounds {
+            row: 4,
+            col: 2,
+            width: 5,
+            height: 4,
+        })
+    );
+    assert_eq!(universe.population(), 0);
+    assert!(!universe.undo());
+    assert_eq!(universe.set_cell(3, 4, Cell::Alive), Ok(()));
+}
+
+#[wasm_bindgen_test]
+fn test_game_error_variants() {
+    assert_eq!(
+        Universe::from_cells(2, 2, &[1, 0, 1]).unwrap_err(),
+        GameError::DimensionMismatch {
+            expected: 4,
+            actual: 3,
+        }
+    );
+    assert_eq!(
+        Universe::from_cells(0, 2, &[]).unwrap_err(),
+        GameError::InvalidDimensions {
+            width: 0,
+            height: 2,
+        }
+    );
+    let mut universe = Universe::empty(4, 4);
+    assert!(matches!(
+        universe.set_rule_string("B9/S23"),
+        Err(GameError::Parse(_))
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_init_is_idempotent() {
+    init();
+    init();
+    assert_eq!(Universe::empty(2, 2).population(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_tick_timed_returns_elapsed_time() {
+    let mut universe = Universe::new_with_size(32, 32);
+    let elapsed = universe.tick_timed();
+
+    assert!(elapsed.is_finite() && elapsed >= 0.0);
+    assert_eq!(universe.generation(), 1);
+}
+
+#[wasm_bindgen_test]
+fn test_insert_ascii_glider() {
+    // The ragged first line is padded with dead cells.
+    let mut universe = Universe::empty(8, 8);
+    universe.insert_ascii(".#\n..#\n###\n", 2, 3, '#');
+
+    assert_eq!(universe.population(), 5);
+    assert!(matches_pattern(&universe, 2, 3, &GLIDER));
+}
+
+#[wasm_bindgen_test]
+fn test_cell_toggle_and_is_alive() {
+    assert_eq!(Cell::Dead.toggle(), Cell::Alive);
+    assert_eq!(Cell::Alive.toggle(), Cell::Dead);
+    assert!(Cell::Alive.is_alive());
+    assert!(!Cell::Dead.is_alive());
+}
+
+#[wasm_bindgen_test]
+fn test_cell_bool_round_trip() {
+    assert_eq!(Cell::from(true), Cell::Alive);
+    assert_eq!(Cell::from(false), Cell::Dead);
+    assert!(bool::from(Cell::from(true)));
+    assert!(!bool::from(Cell::from(false)));
+}
+
+#[wasm_bindgen_test]
+fn test_builder_configures_universe() {
+    let universe = UniverseBuilder::new()
+        .width(32)
+        .height(32)
+        .rule("B36/S23")
+        .topology(Topology::Toroidal)
+        .seed(7)
+        .density(0.3)
+        .build()
+        .unwrap();
+
+    assert_eq!(universe.width(), 32);
+    assert_eq!(universe.height(), 32);
+    assert_eq!(universe.rule_string(), "B36/S23");
+    assert_eq!(universe.topology(), Topology::Toroidal);
+    let mut expected = Universe::new_with_size(32, 32);
+    expected.randomize(7, 0.3);
+    assert_eq!(universe, expected);
+}
+
+#[wasm_bindgen_test]
+fn test_builder_defaults_match_new() {
+    let universe = UniverseBuilder::new().build().unwrap();
+    assert_eq!(universe, Universe::new());
+    assert_eq!(universe.rule_string(), "B3/S23");
+
+    assert!(UniverseBuilder::new().width(0).build().is_err());
+    assert!(UniverseBuilder::new().rule("B3").build().is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_immortal_walls_persist() {
+    // A lone wall cell would die at once, and a dead immortal cell stays empty
+    // even with three live neighbors.
+    let mut universe = Universe::empty(10, 10);
+    for col in 0..10 {
+        universe.set_cell(0, col, Cell::Alive).unwrap();
+        universe.set_immortal(0, col, true);
+    }
+    universe.set_cell(5, 5, Cell::Alive).unwrap();
+    universe.set_immortal(5, 5, true);
+    universe.set_immortal(1, 4, true);
+
+    universe.tick_n(50);
+    for col in 0..10 {
+        assert!(is_alive(&universe, 0, col));
+    }
+    assert!(is_alive(&universe, 5, 5));
+    assert!(!is_alive(&universe, 1, 4));
+    assert!(universe.is_immortal(1, 4));
+
+    universe.set_immortal(5, 5, false);
+    universe.tick();
+    assert!(!is_alive(&universe, 5, 5));
+}
+
+#[wasm_bindgen_test]
+fn test_run_until_stops_when_callback_returns_true() {
+    let mut universe = Universe::new_with_size(16, 16);
+    let mut calls = 0;
+    let mut generations = Vec::new();
+    universe.run_until(|u| {
+        calls += 1;
+        generations.push(u.generation());
+        calls == 10
+    });
+
+    assert_eq!(calls, 10);
+    assert_eq!(universe.generation(), 10);
+    assert_eq!(generations, (1..=10).collect::<Vec<u64>>());
+}
+
+#[wasm_bindgen_test]
+fn test_tick_until_stable_counts_ticks() {
+    // The diagonal's ends die at once, then the lone middle cell dies too.
+    let mut universe = Universe::empty(8, 8);
+    stamp(&mut universe, 2, 2, &["O..", ".O.", "..O"]);
+    assert_eq!(universe.tick_until_stable(100), 2);
+    assert_eq!(universe.population(), 0);
+    assert_eq!(universe.generation(), 2);
+
+    // A blinker is caught once it's back in its starting phase.
+    let mut blinker = Universe::empty(8, 8);
+    blinker.insert_pattern(Pattern::Blinker, 3, 2);
+    assert_eq!(blinker.tick_until_stable(100), 2);
+
+    // A glider on a large board doesn't settle within a few ticks.
+    let mut glider = Universe::empty(32, 32);
+    glider.insert_glider(0, 0);
+    assert_eq!(glider.tick_until_stable(5), 5);
+}
+
+#[wasm_bindgen_test]
+fn test_memory_bytes_scales_with_cells() {
+    let small = Universe::empty(64, 64).memory_bytes();
+    let medium = Universe::empty(128, 64).memory_bytes();
+    let large = Universe::empty(128, 128).memory_bytes();
+
+    assert!(small < medium && medium < large);
+    assert_eq!(large - medium, 2 * (medium - small));
+
+    // Undo snapshots add to the total.
+    let mut universe = Universe::empty(64, 64);
+    universe.tick();
+    assert!(universe.memory_bytes() > small);
+}
+
+#[wasm_bindgen_test]
+fn test_to_pbm_writes_header_and_rows() {
+    let mut universe = Universe::empty(4, 3);
+    stamp(&mut universe, 0, 0, &["O..O", ".OO.", "...."]);
+
+    assert_eq!(universe.to_pbm(), "P1\n4 3\n1001\n0110\n0000\n");
+}
+
+#[wasm_bindgen_test]
+fn test_count_gliders_finds_separated_gliders() {
+    let mut universe = Universe::empty(32, 32);
+    universe.insert_glider(2, 2);
+    universe.insert_pattern_transformed(Pattern::Glider, 15, 20, 2, true);
+    assert_eq!(universe.count_gliders(), 2);
+
+    // Every phase is recognized as the gliders move.
+    for _ in 0..4 {
+        universe.tick();
+        assert_eq!(universe.count_gliders(), 2);
+    }
+
+    // Still lifes and oscillators aren't gliders.
+    let mut other = Universe::empty(16, 16);
+    other.insert_pattern(Pattern::Beacon, 2, 2);
+    other.insert_pattern(Pattern::Toad, 9, 9);
+    assert_eq!(other.count_gliders(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_paint_disc_radius_one_is_a_plus() {
+    let mut universe = Universe::empty(7, 7);
+    universe.paint_disc(3, 3, 1, Cell::Alive);
+    assert!(matches_pattern(&universe, 2, 2, &[".O.", "OOO", ".O."]));
+    assert_eq!(universe.population(), 5);
+}
+
+#[wasm_bindgen_test]
+fn test_paint_disc_follows_topology() {
+    // Radius 2 covers 13 cells; at the corner they wrap or get clipped.
+    let mut toroidal = Universe::empty(8, 8);
+    toroidal.paint_disc(0, 0, 2, Cell::Alive);
+    assert_eq!(toroidal.population(), 13);
+    assert!(is_alive(&toroidal, 7, 7));
+
+    let mut bounded = Universe::empty(8, 8);
+    bounded.set_topology(Topology::Bounded);
+    bounded.paint_disc(0, 0, 2, Cell::Alive);
+    assert_eq!(bounded.population(), 6);
+}
+
+#[wasm_bindgen_test]
+fn test_downsample_averages_blocks() {
+    // Alternating columns make every 4x4 block half alive.
+    let mut universe = Universe::empty(16, 8);
+    for row in 0..8 {
+        for col in (0..16).step_by(2) {
+            universe.set_cell(row, col, Cell::Alive).unwrap();
+        }
+    }
+    let thumbnail = universe.downsample(4, 2);
+    assert_eq!(thumbnail.len(), 8);
+    assert!(thumbnail.iter().all(|&gray| (126..=129).contains(&gray)));
+
+    universe.fill_rect(0, 0, 16, 8, Cell::Alive);
+    assert_eq!(universe.downsample(2, 2), vec![255; 4]);
+    universe.clear();
+    assert_eq!(universe.downsample(32, 16), vec![0; 512]);
+}
+
+#[wasm_bindgen_test]
+fn test_neighbor_mask_bit_order() {
+    let mut universe = Universe::empty(5, 5);
+    universe.set_cell(1, 1, Cell::Alive).unwrap(); // NW, bit 0
+    universe.set_cell(2, 3, Cell::Alive).unwrap(); // E, bit 4
+    universe.set_cell(3, 2, Cell::Alive).unwrap(); // S, bit 6
+    assert_eq!(universe.neighbor_mask(2, 2), 0b0101_0001);
+
+    // The corner's NW neighbor wraps to the opposite corner unless bounded.
+    universe.set_cell(4, 4, Cell::Alive).unwrap();
+    assert_eq!(universe.neighbor_mask(0, 0), 0b1000_0001);
+    universe.set_topology(Topology::Bounded);
+    assert_eq!(universe.neighbor_mask(0, 0), 0b1000_0000);
+}
+
+#[wasm_bindgen_test]
+fn test_randomize_rect_stays_inside_region() {
+    let mut universe = Universe::new_with_size(12, 12);
+    let before = universe.clone();
+    universe.randomize_rect(0, 0, 4, 4, 99, 0.5);
+
+    for row in 0..12 {
+        for col in 0..12 {
+            if row >= 4 || col >= 4 {
+                assert_eq!(universe.get_cell(row, col), before.get_cell(row, col));
+            }
+        }
+    }
+    assert_ne!(universe, before);
+
+    let mut again = before.clone();
+    again.randomize_rect(0, 0, 4, 4, 99, 0.5);
+    assert_eq!(again, universe);
+}
+
+#[wasm_bindgen_test]
+fn test_storage_order_does_not_change_ticks() {
+    let mut row_major = Universe::new_with_size(13, 9);
+    row_major.randomize(5, 0.4);
+    let mut column_major = row_major.clone();
+    column_major.set_storage_order(StorageOrder::ColumnMajor);
+    assert_eq!(column_major.storage_order(), StorageOrder::ColumnMajor);
+    assert_eq!(column_major, row_major);
+
+    for _ in 0..10 {
+        row_major.tick();
+        column_major.tick();
+        assert_eq!(column_major.render(), row_major.render());
+        assert_eq!(
+            column_major.changed_cells().len(),
+            row_major.changed_cells().len()
+        );
+    }
+    assert_eq!(column_major.live_cells(), row_major.live_cells());
+    assert_eq!(column_major.state_hash(), row_major.state_hash());
+
+    // Larger neighborhoods and bounded edges go through the same indexing.
+    for universe in [&mut row_major, &mut column_major].iter_mut() {
+        universe.set_topology(Topology::Bounded);
+        universe.set_neighbor_radius(2);
+        universe.set_rule(&[6, 7], &[5, 6, 7, 8]);
+        universe.tick_n(3);
+    }
+    assert_eq!(column_major, row_major);
+    assert_eq!(column_major.to_bytes(), row_major.to_bytes());
+}
+
+#[wasm_bindgen_test]
+fn test_wireworld_electron_moves_along_wire() {
+    // A rectangular loop of wire with an electron heading right along the top.
+    let mut universe = Universe::empty(10, 6);
+    universe.set_automaton(Automaton::Wireworld);
+    for col in 1..9 {
+        universe.set_cell_state(1, col, 1);
+        universe.set_cell_state(4, col, 1);
+    }
+    for row in 2..4 {
+        universe.set_cell_state(row, 1, 1);
+        universe.set_cell_state(row, 8, 1);
+    }
+    universe.set_cell_state(1, 2, 2);
+    universe.set_cell_state(1, 3, 3);
+
+    for step in 1..=4 {
+        universe.tick();
+        assert_eq!(
+            universe.cell_state(1, 3 + step),
+            3,
+            "head after {} ticks",
+            step
+        );
+        assert_eq!(universe.cell_state(1, 2 + step), 2);
+        assert_eq!(universe.cell_state(1, 1 + step), 1);
+        assert_eq!(universe.population(), 1);
+    }
+
+    // The electron keeps circulating instead of dying out, and the empty
+    // inside of the loop stays empty.
+    universe.tick_n(40);
+    assert!(universe.population() >= 1);
+    assert_eq!(universe.cell_state(2, 4), 0);
+    assert_eq!(universe.states(), 4);
+}
+
+#[wasm_bindgen_test]
+fn test_rule_preset_seeds() {
+    // Under Seeds (B2/S) nothing survives and cells with exactly two live
+    // neighbors are born, so a domino turns into the two cells flanking it
+    // above and below on each side.
+    let mut universe = Universe::empty(8, 8);
+    universe.set_rule_preset(RulePreset::Seeds);
+    assert_eq!(universe.rule_string(), "B2/S");
+    universe.set_cells(&[3, 3, 3, 4]);
+    universe.tick();
+    assert_eq!(universe.live_cells(), vec![2, 3, 2, 4, 4, 3, 4, 4]);
+
+    // A lone cell has no neighbors at all and simply dies.
+    let mut universe = Universe::empty(8, 8);
+    universe.set_rule_preset(RulePreset::Seeds);
+    universe.set_cells(&[4, 4]);
+    universe.tick();
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_rule_preset_strings() {
+    let mut universe = Universe::empty(4, 4);
+    let presets = [
+        (RulePreset::Conway, "B3/S23"),
+        (RulePreset::HighLife, "B36/S23"),
+        (RulePreset::DayNight, "B3678/S34678"),
+        (RulePreset::Seeds, "B2/S"),
+        (RulePreset::Replicator, "B1357/S1357"),
+    ];
+    for (preset, rule) in presets.iter() {
+        universe.set_rule_preset(*preset);
+        assert_eq!(universe.rule_string(), *rule);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_tick_into_matches_tick() {
+    let mut universe = Universe::empty(12, 10);
+    universe.insert_pattern(Pattern::Glider, 1, 1);
+    universe.insert_pattern(Pattern::Blinker, 6, 8);
+
+    // A buffer of the wrong size gets resized and fully overwritten.
+    let mut out = vec![Cell::Alive; 3];
+    for _ in 0..6 {
+        universe.tick_into(&mut out);
+        let before = universe.live_cells();
+
+        let mut expected = universe.clone();
+        expected.tick();
+        assert_eq!(universe.live_cells(), before, "tick_into must not advance");
+        assert_eq!(out.len(), 120);
+        for row in 0..10 {
+            for col in 0..12 {
+                assert_eq!(out[(row * 12 + col) as usize], expected.get_cell(row, col));
+            }
+        }
+        universe = expected;
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_fill_soup_is_deterministic() {
+    let mut a = Universe::empty(32, 32);
+    let mut b = Universe::empty(32, 32);
+    a.randomize(1, 0.5);
+    a.fill_soup("k_soup");
+    b.fill_soup("k_soup");
+    assert_eq!(a.live_cells(), b.live_cells());
+    assert_eq!(a.generation(), 0);
+
+    b.fill_soup("k_other");
+    assert_ne!(a.live_cells(), b.live_cells());
+
+    // Everything lands in the centred 16x16 square.
+    assert!(a.live_cells().iter().all(|&coord| (8..24).contains(&coord)));
+}
+
+#[wasm_bindgen_test]
+fn test_fill_soup_matches_sha256_layout() {
+    // SHA-256("abc") starts ba 78 16 bf: bytes alternate between the left
+    // and right halves of each row, most significant bit first.
+    let mut universe = Universe::empty(16, 16);
+    universe.fill_soup("abc");
+    assert_eq!(universe.population(), 120);
+    assert_eq!(
+        &universe.live_cells()[..12],
+        &[0, 0, 0, 2, 0, 3, 0, 4, 0, 6, 0, 9]
+    );
+    let row0: Vec<bool> = (0..16)
+        .map(|col| universe.get_cell(0, col) == Cell::Alive)
+        .collect();
+    let expected: Vec<bool> = (0..16)
+        .map(|col| [0xbau8, 0x78][col / 8] & (0x80 >> (col % 8)) != 0)
+        .collect();
+    assert_eq!(row0, expected);
+}
+
+#[wasm_bindgen_test]
+fn test_pending_changes_previews_next_tick() {
+    // A horizontal blinker at (2,1)-(2,3): its ends die and the cells above
+    // and below the middle are born.
+    let mut universe = Universe::empty(5, 5);
+    universe.set_cells(&[2, 1, 2, 2, 2, 3]);
+    let pending = universe.pending_changes();
+    assert_eq!(pending, vec![7, 11, 13, 17]);
+    assert_eq!(universe.generation(), 0);
+
+    universe.tick();
+    assert_eq!(universe.changed_cells(), pending);
+}
+
+#[wasm_bindgen_test]
+fn test_tick_on_empty_board() {
+    let mut universe = Universe::empty(32, 32);
+    universe.randomize(9, 0.4);
+    universe.tick();
+    universe.clear();
+    for generation in 1..=5 {
+        universe.tick();
+        assert_eq!(universe.population(), 0);
+        assert_eq!(universe.generation(), generation);
+        assert!(universe.changed_cells().is_empty());
+    }
+    assert_eq!(universe.population_history(), vec![0; 5]);
+
+    // A B0 rule still fills an empty board on the next tick.
+    universe.set_rule(&[0], &[]);
+    universe.tick();
+    assert_eq!(universe.population(), 32 * 32);
+
+    // Dying cells of a Generations rule keep decaying on a board with no
+    // live cells left.
+    let mut universe = Universe::empty(8, 8);
+    universe.set_states(4);
+    universe.set_cell_state(3, 3, 2);
+    assert_eq!(universe.population(), 0);
+    universe.tick();
+    assert_eq!(universe.cell_state(3, 3), 1);
+    universe.tick();
+    assert_eq!(universe.cell_state(3, 3), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_population_counter_matches_recount() {
+    let recount = |universe: &Universe| universe.live_cells().len() as u32 / 2;
+    let mut universe = Universe::new_with_size(24, 20);
+    assert_eq!(universe.population(), recount(&universe));
+
+    let mut rng = 12345u32;
+    let mut next = |bound: u32| {
+        rng = rng.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        (rng >> 8) % bound
+    };
+    for step in 0..200 {
+        let (row, col) = (next(20), next(24));
+        match next(8) {
+            0 => universe.toggle_cell(row, col),
+            1 => universe.set_cell(row, col, Cell::Alive).unwrap(),
+            2 => universe.set_cell(row, col, Cell::Dead).unwrap(),
+            3 => universe.insert_pattern(Pattern::Glider, row, col),
+            4 => universe.fill_rect(row, col, 3, 2, Cell::Alive),
+            5 => {
+                universe.undo();
+            }
+            6 => universe.set_cell_state(row, col, 1),
+            _ => universe.tick(),
+        }
+        assert_eq!(universe.population(), recount(&universe), "step {}", step);
+    }
+
+    universe.randomize(3, 0.3);
+    assert_eq!(universe.population(), recount(&universe));
+    universe.resize(10, 10);
+    assert_eq!(universe.population(), recount(&universe));
+    universe.rotate_cw();
+    universe.tick_n(5);
+    assert_eq!(universe.population(), recount(&universe));
+    universe.clear();
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_tick_region_only_updates_inside() {
+    // A blinker in the top-left corner and another far away: ticking only
+    // the first one's 3x3 bounding box flips it while the other stays put.
+    let mut universe = Universe::empty(16, 16);
+    universe.set_cells(&[2, 1, 2, 2, 2, 3, 10, 10, 10, 11, 10, 12]);
+    universe.tick_region(1, 1, 3, 3);
+    assert_eq!(
+        universe.live_cells(),
+        vec![1, 2, 2, 2, 3, 2, 10, 10, 10, 11, 10, 12]
+    );
+    assert_eq!(universe.generation(), 0);
+    assert_eq!(universe.changed_cells(), vec![18, 33, 35, 50]);
+    assert_eq!(universe.population(), 6);
+
+    // Cells just outside the region count as neighbors but never change.
+    let mut universe = Universe::empty(8, 8);
+    universe.set_cells(&[3, 2, 3, 3, 3, 4]);
+    universe.tick_region(3, 3, 1, 1);
+    assert_eq!(universe.live_cells(), vec![3, 2, 3, 3, 3, 4]);
+    universe.tick_region(2, 3, 1, 1);
+    assert_eq!(universe.live_cells(), vec![2, 3, 3, 2, 3, 3, 3, 4]);
+}
+
+#[wasm_bindgen_test]
+fn test_drift_over_glider() {
+    let mut universe = Universe::empty(16, 16);
+    universe.insert_pattern(Pattern::Glider, 4, 4);
+    let (drow, dcol) = universe.drift_over(4);
+    assert!((drow - 1.0).abs() < 1e-9, "drow = {}", drow);
+    assert!((dcol - 1.0).abs() < 1e-9, "dcol = {}", dcol);
+    assert_eq!(universe.generation(), 4);
+
+    // Crossing the bottom-right seam still reads as a small step forward.
+    let mut universe = Universe::empty(16, 16);
+    universe.insert_pattern(Pattern::Glider, 13, 13);
+    let (drow, dcol) = universe.drift_over(16);
+    assert!((drow - 4.0).abs() < 1e-9, "drow = {}", drow);
+    assert!((dcol - 4.0).abs() < 1e-9, "dcol = {}", dcol);
+
+    // A still life doesn't drift.
+    let mut universe = Universe::empty(8, 8);
+    universe.set_cells(&[3, 3, 3, 4, 4, 3, 4, 4]);
+    assert_eq!(universe.drift_over(3), (0.0, 0.0));
+}
+
+#[wasm_bindgen_test]
+fn test_overlay_merges_boards() {
+    let mut glider = Universe::empty(3, 3);
+    glider.insert_pattern(Pattern::Glider, 0, 0);
+
+    let mut universe = Universe::empty(20, 20);
+    universe.set_cells(&[0, 0]);
+    universe.overlay(&glider, 5, 7);
+    let expected: Vec<u32> = glider
+        .live_cells()
+        .chunks(2)
+        .flat_map(|cell| vec![cell[0] + 5, cell[1] + 7])
+        .collect();
+    assert_eq!(universe.population(), 6);
+    assert_eq!(universe.live_cells()[2..], expected[..]);
+
+    // Overlaying again is a no-op, and a single undo removes the merge.
+    universe.overlay(&glider, 5, 7);
+    assert_eq!(universe.population(), 6);
+    universe.undo();
+    universe.undo();
+    assert_eq!(universe.live_cells(), vec![0, 0]);
+
+    // Cells hanging off the bottom-right edge wrap around.
+    universe.overlay(&glider, 19, 19);
+    assert_eq!(universe.population(), 6);
+}
+
+#[wasm_bindgen_test]
+fn test_hashlife_matches_dense_engine() {
+    // A blinker and a block, well away from the dense board's edges.
+    let mut dense = Universe::empty(16, 16);
+    dense.set_cells(&[6, 5, 6, 6, 6, 7, 2, 2, 2, 3, 3, 2, 3, 3]);
+    let mut hashlife = HashlifeUniverse::new();
+    for cell in dense.live_cells().chunks(2) {
+        hashlife.set_cell(cell[0] as i64, cell[1] as i64, Cell::Alive);
+    }
+    let as_i64 = |universe: &Universe| -> Vec<i64> {
+        universe.live_cells().iter().map(|&c| c as i64).collect()
+    };
+
+    dense.tick();
+    hashlife.tick();
+    assert_eq!(hashlife.live_cells(), as_i64(&dense));
+    assert_eq!(hashlife.generation(), 1);
+
+    dense.tick_n(8);
+    hashlife.advance(3);
+    assert_eq!(hashlife.live_cells(), as_i64(&dense));
+    assert_eq!(hashlife.population(), 7);
+    assert_eq!(hashlife.generation(), 9);
+    assert_eq!(hashlife.get_cell(5, 6), Cell::Alive);
+    assert_eq!(hashlife.get_cell(6, 5), Cell::Dead);
+}
+
+#[wasm_bindgen_test]
+fn test_hashlife_matches_sparse_engine() {
+    // The R-pentomino grows chaotically for over a thousand generations.
+    let mut sparse = SparseUniverse::new();
+    let mut hashlife = HashlifeUniverse::new();
+    for &(row, col) in &[(0, 1), (0, 2), (1, 0), (1, 1), (2, 1)] {
+        sparse.set_cell(row, col, Cell::Alive);
+        hashlife.set_cell(row, col, Cell::Alive);
+    }
+    for &n in &[1, 7, 32, 60, 100] {
+        sparse.tick_n(n as u32);
+        hashlife.tick_n(n);
+        assert_eq!(
+            hashlife.live_cells(),
+            sparse.live_cells(),
+            "gen {}",
+            hashlife.generation()
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_hashlife_advances_exponentially() {
+    // 2^40 generations move a glider 2^38 cells down and right.
+    let mut hashlife = HashlifeUniverse::new();
+    hashlife.insert_pattern(Pattern::Glider, 0, 0);
+    let start = hashlife.live_cells();
+    hashlife.advance(40);
+    let moved: Vec<i64> = start.iter().map(|&coord| coord + (1 << 38)).collect();
+    assert_eq!(hashlife.live_cells(), moved);
+    assert_eq!(hashlife.generation(), 1 << 40);
+}
+
+#[wasm_bindgen_test]
+fn test_render_region_around_blinker() {
+    let mut universe = Universe::empty(32, 32);
+    universe.set_cells(&[10, 19, 10, 20, 10, 21]);
+    assert_eq!(universe.render_region(9, 19, 3, 3), "◻◻◻\n◼◼◼\n◻◻◻\n");
+    universe.tick();
+    assert_eq!(universe.render_region(9, 19, 3, 3), "◻◼◻\n◻◼◻\n◻◼◻\n");
+
+    // Clipped at the bottom-right corner, and empty when entirely outside.
+    assert_eq!(universe.render_region(30, 30, 5, 5), "◻◻\n◻◻\n");
+    assert_eq!(universe.render_region(40, 0, 3, 3), "");
+    assert_eq!(universe.render_region(0, 0, 32, 32), universe.render());
+}
+
+#[wasm_bindgen_test]
+fn test_new_seeding_formula() {
+    let universe = Universe::new();
+    let expected = (0..64 * 64).filter(|i| i % 2 == 0 || i % 7 == 0).count() as u32;
+    assert_eq!(expected, 2341);
+    assert_eq!(universe.population(), expected);
+
+    // Odd widths carry the index across rows, so row 1 starts on a dead cell
+    // (index 5) and picks up the multiple of 7 in its middle.
+    let universe = Universe::new_with_size(5, 3);
+    assert_eq!(universe.render_with('#', '.'), "#.#.#\n.###.\n#.#.#\n");
+
+    // `reset` reproduces the same board, also in column-major storage.
+    let mut universe = Universe::new_with_size(9, 7);
+    let seeded = universe.live_cells();
+    universe.set_storage_order(StorageOrder::ColumnMajor);
+    universe.clear();
+    universe.reset();
+    assert_eq!(universe.live_cells(), seeded);
+}
+
+#[wasm_bindgen_test]
+fn test_population_in_rect() {
+    // A glider's 3x3 box holds all five cells; a box one cell too small on
+    // the left misses the cell in its first column.
+    let mut universe = Universe::empty(20, 12);
+    universe.insert_pattern(Pattern::Glider, 4, 6);
+    universe.set_cells(&[11, 19]);
+    assert_eq!(universe.population_in_rect(4, 6, 3, 3), 5);
+    assert_eq!(universe.population_in_rect(4, 7, 2, 3), 4);
+    assert_eq!(universe.population_in_rect(0, 0, 10, 10), 5);
+    assert_eq!(universe.population_in_rect(0, 0, 100, 100), 6);
+    assert_eq!(universe.population_in_rect(10, 18, 5, 5), 1);
+    assert_eq!(universe.population_in_rect(12, 0, 5, 5), 0);
+    assert_eq!(universe.population_in_rect(4, 6, 0, 3), 0);
+
+    universe.set_storage_order(StorageOrder::ColumnMajor);
+    assert_eq!(universe.population_in_rect(4, 7, 2, 3), 4);
+    assert_eq!(universe.population_in_rect(10, 18, 5, 5), 1);
+}
+
+#[wasm_bindgen_test]
+fn test_nearest_live() {
+    let mut universe = Universe::empty(100, 80);
+    assert_eq!(universe.nearest_live(10, 10), None);
+
+    // A single cell is found from the far side of the board.
+    universe.set_topology(Topology::Bounded);
+    universe.set_cells(&[70, 90]);
+    assert_eq!(universe.nearest_live(0, 0), Some(70 * 100 + 90));
+    assert_eq!(universe.nearest_live(70, 90), Some(70 * 100 + 90));
+
+    // The closer of two cells wins, by Chebyshev distance.
+    universe.set_cells(&[5, 20]);
+    assert_eq!(universe.nearest_live(0, 0), Some(5 * 100 + 20));
+    assert_eq!(universe.nearest_live(60, 60), Some(70 * 100 + 90));
+
+    // On a torus the search wraps: (70, 90) is 10 rows up and 10 columns
+    // left of (0, 0), closer than (5, 20).
+    universe.set_topology(Topology::Toroidal);
+    assert_eq!(universe.nearest_live(0, 0), Some(70 * 100 + 90));
+}
+
+#[wasm_bindgen_test]
+fn test_neighbor_cache_matches_plain_tick() {
+    let mut plain = Universe::new_with_size(40, 30);
+    plain.randomize(77, 0.35);
+    let mut cached = plain.clone();
+    cached.set_neighbor_cache(true);
+    assert!(cached.neighbor_cache());
+    assert!(!plain.neighbor_cache());
+
+    let mut check = |step: &str, edit: &dyn Fn(&mut Universe)| {
+        edit(&mut plain);
+        edit(&mut cached);
+        for _ in 0..5 {
+            plain.tick();
+            cached.tick();
+            assert_eq!(cached.live_cells(), plain.live_cells(), "after {}", step);
+        }
+    };
+    check("ticks", &|_| {});
+    check("edits", &|u| {
+        u.toggle_cell(0, 0);
+        u.set_cell(29, 39, Cell::Alive).unwrap();
+        u.insert_pattern(Pattern::Glider, 10, 10);
+        u.fill_rect(20, 20, 4, 4, Cell::Dead);
+    });
+    check("bounded", &|u| u.set_topology(Topology::Bounded));
+    check("radius", &|u| u.set_neighbor_radius(2));
+    check("von neumann", &|u| {
+        u.set_neighborhood(Neighborhood::VonNeumann);
+        u.set_rule(&[2, 3], &[1, 2]);
+    });
+    check("resize", &|u| {
+        u.resize(25, 35);
+        u.randomize(5, 0.4);
+    });
+    check("clear", &|u| {
+        u.clear();
+        u.insert_pattern(Pattern::Pulsar, 5, 5);
+        u.set_neighbor_radius(1);
+        u.set_neighborhood(Neighborhood::Moore);
+        u.set_rule(&[3], &[2, 3]);
+    });
+
+    cached.set_neighbor_cache(false);
+    assert!(!cached.neighbor_cache());
+}
+
+#[wasm_bindgen_test]
+fn test_active_ticking_matches_full_scan() {
+    let mut full = Universe::empty(500, 500);
+    full.set_history_depth(0);
+    full.insert_pattern(Pattern::Glider, 10, 10);
+    let mut active = full.clone();
+    active.set_active_ticking(true);
+    assert!(active.active_ticking());
+
+    for step in 0..40 {
+        full.tick();
+        active.tick();
+        assert_eq!(
+            active.changed_cells(),
+            full.changed_cells(),
+            "tick {}",
+            step
+        );
+    }
+    assert_eq!(active.live_cells(), full.live_cells());
+    assert_eq!(active.population(), 5);
+
+    // Edits, rule changes and new boards between ticks are picked up too.
+    let mut full = Universe::new_with_size(48, 40);
+    full.randomize(21, 0.3);
+    let mut active = full.clone();
+    active.set_active_ticking(true);
+    let mut check = |edit: &dyn Fn(&mut Universe)| {
+        edit(&mut full);
+        edit(&mut active);
+        for _ in 0..6 {
+            full.tick();
+            active.tick();
+            assert_eq!(active.live_cells(), full.live_cells());
+        }
+        for row in 0..40 {
+            for col in 0..48 {
+                assert_eq!(active.age(row, col), full.age(row, col));
+            }
+        }
+    };
+    check(&|_| {});
+    check(&|u| {
+        u.toggle_cell(3, 3);
+        u.insert_pattern(Pattern::Pulsar, 20, 20);
+    });
+    check(&|u| u.set_rule(&[3, 6], &[2, 3]));
+    check(&|u| u.set_topology(Topology::Bounded));
+    check(&|u| u.set_states(3));
+    check(&|u| u.set_states(2));
+    check(&|u| {
+        u.undo();
+        u.set_immortal(5, 5, true);
+        u.set_cell(5, 5, Cell::Alive).unwrap();
+    });
+    check(&|u| u.set_immortal(5, 5, false));
+}
+
+#[wasm_bindgen_test]
+fn test_is_empty_and_is_full() {
+    let mut universe = Universe::new_with_size(6, 4);
+    assert!(!universe.is_empty());
+    assert!(!universe.is_full());
+
+    universe.clear();
+    assert!(universe.is_empty());
+    assert!(!universe.is_full());
+
+    universe.fill_rect(0, 0, 6, 4, Cell::Alive);
+    assert!(universe.is_full());
+    assert!(!universe.is_empty());
+
+    universe.toggle_cell(2, 2);
+    assert!(!universe.is_full());
+}
+
+#[wasm_bindgen_test]
+fn test_difference_counts_changed_cells() {
+    let mut universe = Universe::new_with_size(30, 20);
+    universe.randomize(8, 0.4);
+    assert_eq!(universe.difference(&universe.clone()), Ok(0));
+
+    let mut ticked = universe.clone();
+    ticked.tick();
+    let changed = ticked.changed_cells().len() as u32;
+    assert!(changed > 0);
+    assert_eq!(universe.difference(&ticked), Ok(changed));
+    assert_eq!(ticked.difference(&universe), Ok(changed));
+
+    // The storage order of either board doesn't matter.
+    ticked.set_storage_order(StorageOrder::ColumnMajor);
+    assert_eq!(universe.difference(&ticked), Ok(changed));
+
+    let other = Universe::new_with_size(20, 30);
+    assert_eq!(
+        universe.difference(&other),
+        Err(GameError::SizeMismatch {
+            width: 30,
+            height: 20,
+            other_width: 20,
+            other_height: 30,
+        })
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_fallible_constructors_reject_overflowing_dimensions() {
+    let too_big = GameError::InvalidDimensions {
+        width: 65536,
+        height: 65536,
+    };
+    assert_eq!(
+        Universe::from_cells(65536, 65536, &[]).err(),
+        Some(too_big.clone())
+    );
+    assert_eq!(
+        UniverseBuilder::new()
+            .width(65536)
+            .height(65536)
+            .build()
+            .err(),
+        Some(too_big.clone())
+    );
+    let mut bytes = Universe::empty(2, 2).to_bytes();
+    bytes[5..9].copy_from_slice(&65536u32.to_le_bytes());
+    bytes[9..13].copy_from_slice(&65536u32.to_le_bytes());
+    assert_eq!(Universe::from_bytes(&bytes).err(), Some(too_big));
+}
+
+#[wasm_bindgen_test]
+fn test_load_rle_rejects_oversized_patterns() {
+    let mut universe = Universe::empty(8, 8);
+    universe.insert_pattern(Pattern::Glider, 0, 0);
+    for rle in &[
+        "4294967295b2o!",
+        "3o4294967295$o!",
+        "x = 100000, y = 100000\no!",
+        "70000$70000bo!",
+    ] {
+        match universe.load_rle(rle) {
+            Err(GameError::Parse(_)) => {}
+            other => panic!("{:?} gave {:?}", rle, other),
+        }
+    }
+    // A rejected pattern leaves the board as it was.
+    assert_eq!((universe.width(), universe.height()), (8, 8));
+    assert_eq!(universe.population(), 5);
+}
+
+#[wasm_bindgen_test]
+fn test_load_life106_rejects_oversized_patterns() {
+    let mut universe = Universe::empty(8, 8);
+    universe.set_cells(&[1, 1]);
+    for text in &[
+        "#Life 1.06\n0 0\n100000 100000\n",
+        "#Life 1.06\n0 -4294967296\n0 1\n",
+        "#Life 1.06\n-9223372036854775808 0\n9223372036854775807 0\n",
+    ] {
+        match universe.load_life106(text) {
+            Err(GameError::Parse(_)) => {}
+            other => panic!("{:?} gave {:?}", text, other),
+        }
+    }
+    assert_eq!((universe.width(), universe.height()), (8, 8));
+    assert_eq!(universe.live_cells(), vec![1, 1]);
+
+    // Empty files still clear the board.
+    universe.load_life106("#Life 1.06\n").unwrap();
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_immortal_cells_count_as_stable() {
+    // A lone cell would die, but an immortal one never changes.
+    let mut universe = Universe::empty(6, 6);
+    universe.set_cell(2, 2, Cell::Alive).unwrap();
+    assert!(!universe.is_stable());
+
+    universe.set_immortal(2, 2, true);
+    assert!(universe.is_stable());
+    assert!(universe.stats().stable);
+    assert_eq!(universe.tick_until_stable(10), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_transforms_move_immortal_cells() {
+    let mut universe = Universe::empty(6, 4);
+    universe.set_cell(1, 0, Cell::Alive).unwrap();
+    universe.set_immortal(1, 0, true);
+
+    universe.flip_horizontal();
+    assert!(universe.is_immortal(1, 5));
+    assert!(!universe.is_immortal(1, 0));
+
+    universe.flip_vertical();
+    assert!(universe.is_immortal(2, 5));
+
+    universe.translate(1, 1);
+    assert!(universe.is_immortal(3, 0));
+
+    universe.rotate_cw();
+    assert_eq!((universe.width(), universe.height()), (4, 6));
+    assert!(universe.is_immortal(0, 0));
+    assert_eq!(universe.live_cells(), vec![0, 0]);
+
+    universe.tick();
+    assert_eq!(universe.live_cells(), vec![0, 0]);
+}
+
+#[wasm_bindgen_test]
+fn test_wireworld_stability_follows_wireworld_rules() {
+    // Under Life a 2x2 block is a still life, but electron heads become tails.
+    let mut universe = Universe::empty(6, 6);
+    universe.set_automaton(Automaton::Wireworld);
+    for &(row, col) in &[(2, 2), (2, 3), (3, 2), (3, 3)] {
+        universe.set_cell_state(row, col, 3);
+    }
+    assert!(!universe.is_stable());
+    assert!(!universe.stats().stable);
+
+    // Heads -> tails -> conductors, then nothing changes.
+    assert_eq!(universe.tick_until_stable(10), 2);
+    assert_eq!(universe.cell_state(2, 2), 1);
+    assert!(universe.is_stable());
+
+    // A lone tail has no live cells but still turns into a conductor.
+    let mut tail = Universe::empty(6, 6);
+    tail.set_automaton(Automaton::Wireworld);
+    tail.set_cell_state(1, 1, 2);
+    assert!(!tail.is_stable());
+    assert_eq!(tail.tick_until_stable(10), 1);
+}
+
+#[wasm_bindgen_test]
+fn test_dying_cells_are_not_stable() {
+    let mut universe = Universe::empty(6, 6);
+    universe.set_states(4);
+    universe.set_cell(2, 2, Cell::Alive).unwrap();
+    universe.tick();
+    assert_eq!(universe.population(), 0);
+    assert!(!universe.is_stable());
+    assert_eq!(universe.tick_until_stable(10), 2);
+}
+
+#[wasm_bindgen_test]
+fn test_hashlife_caps_advance_at_two_to_the_63() {
+    // 2^63 generations move a glider 2^61 cells down and right.
+    let mut hashlife = HashlifeUniverse::new();
+    hashlife.insert_pattern(Pattern::Glider, 0, 0);
+    let start = hashlife.live_cells();
+    hashlife.advance(255);
+    let moved: Vec<i64> = start.iter().map(|&coord| coord + (1 << 61)).collect();
+    assert_eq!(hashlife.live_cells(), moved);
+    assert_eq!(hashlife.generation(), 1 << 63);
+}
+
+#[wasm_bindgen_test]
+fn test_wireworld_wires_survive_undo_and_transforms() {
+    let states = |universe: &Universe, row: u32| -> Vec<u8> {
+        (0..8).map(|col| universe.cell_state(row, col)).collect()
+    };
+    // Wider than the wire, so the head doesn't reach its far end by wrapping.
+    let mut universe = Universe::empty(10, 4);
+    universe.set_automaton(Automaton::Wireworld);
+    for col in 0..8 {
+        universe.set_cell_state(1, col, if col == 0 { 3 } else { 1 });
+    }
+    let wire = vec![3, 1, 1, 1, 1, 1, 1, 1];
+
+    universe.tick();
+    assert_eq!(states(&universe, 1), vec![2, 3, 1, 1, 1, 1, 1, 1]);
+    assert!(universe.undo());
+    assert_eq!(states(&universe, 1), wire);
+
+    universe.translate(0, 0);
+    assert_eq!(states(&universe, 1), wire);
+    universe.translate(1, 0);
+    assert_eq!(states(&universe, 2), wire);
+    universe.flip_vertical();
+    assert_eq!(states(&universe, 1), wire);
+    universe.flip_horizontal();
+    assert_eq!(universe.cell_state(1, 9), 3);
+    assert_eq!(universe.cell_state(1, 2), 1);
+    assert_eq!(universe.cell_state(1, 0), 0);
+    universe.flip_horizontal();
+
+    universe.resize(12, 5);
+    assert_eq!(states(&universe, 1), wire);
+    universe.set_storage_order(StorageOrder::ColumnMajor);
+    assert_eq!(states(&universe, 1), wire);
+    universe.rotate_cw();
+    assert_eq!(universe.cell_state(0, 3), 3);
+    assert_eq!(universe.cell_state(7, 3), 1);
+
+    let checkpoint = universe.save_checkpoint();
+    universe.tick_n(3);
+    assert!(universe.restore_checkpoint(checkpoint));
+    assert_eq!(universe.cell_state(0, 3), 3);
+    assert_eq!(universe.cell_state(1, 3), 1);
+}
+
+#[wasm_bindgen_test]
+fn test_undo_restores_dying_cells() {
+    let mut universe = Universe::empty(6, 6);
+    universe.set_states(4);
+    universe.set_cell(2, 2, Cell::Alive).unwrap();
+    universe.tick();
+    assert_eq!(universe.cell_state(2, 2), 2);
+    universe.tick();
+    assert_eq!(universe.cell_state(2, 2), 1);
+
+    assert!(universe.undo());
+    assert_eq!(universe.cell_state(2, 2), 2);
+
+    // Dying states the rule no longer has are cut short, as by `set_states`.
+    universe.set_states(2);
+    universe.tick();
+    universe.undo();
+    assert_eq!(universe.cell_state(2, 2), 0);
+    assert!(universe.is_stable());
+}
+
+#[wasm_bindgen_test]
+fn test_diff_next_on_auto_expanding_board() {
+    // A still block beside a blinker touching the left edge, which makes the
+    // board grow on the next tick. The diff is drawn at the current size.
+    let mut universe = Universe::empty(8, 8);
+    universe.set_auto_expand(true);
+    universe.set_cells(&[1, 4, 1, 5, 2, 4, 2, 5, 4, 0, 5, 0, 6, 0]);
+
+    assert_eq!(
+        universe.diff_next(),
+        "........\n....##..\n....##..\n........\n-.......\n#+.....+\n-.......\n........\n"
+    );
+    assert_eq!(universe.width(), 8);
+}
+
+#[wasm_bindgen_test]
+fn test_is_periodic_on_auto_expanding_board() {
+    // A blinker touching the edge would grow the board if actually ticked.
+    let mut universe = Universe::empty(8, 8);
+    universe.set_auto_expand(true);
+    universe.set_cells(&[3, 0, 4, 0, 5, 0]);
+
+    assert_eq!(universe.is_periodic(5), Some(2));
+    assert_eq!(universe.width(), 8);
+}
+
+#[wasm_bindgen_test]
+fn test_full_history_undoes_across_resizes() {
+    // Once the history is full its oldest snapshots are recycled, including
+    // for boards of a different size.
+    let mut universe = Universe::new_with_size(12, 10);
+    universe.set_history_depth(3);
+    let mut before_ticks = Vec::new();
+    for step in 0..6 {
+        if step == 4 {
+            universe.resize(16, 14);
+        }
+        before_ticks.push(universe.render());
+        universe.tick();
+    }
+
+    for expected in before_ticks.iter().rev().take(3) {
+        assert!(universe.undo());
+        assert_eq!(&universe.render(), expected);
+    }
+    assert!(!universe.undo());
+    assert_eq!((universe.width(), universe.height()), (12, 10));
+
+    let mut next = universe.next_generation();
+    assert_eq!(next.generation(), universe.generation() + 1);
+    assert!(next.undo());
+    assert_eq!(next.render(), universe.render());
+    assert!(!next.undo());
+}
+
+#[cfg(feature = "parallel")]
+#[wasm_bindgen_test]
+fn test_parallel_tick_matches_serial_engine() {
+    // `tick_into` always runs serially, so stepping a copy of the board with
+    // it must give exactly the cells the parallel `tick` produces.
+    for &order in &[StorageOrder::RowMajor, StorageOrder::ColumnMajor] {
+        let mut parallel = Universe::new_with_size(67, 41);
+        parallel.randomize(2024, 0.35);
+        parallel.set_storage_order(order);
+        let mut serial = parallel.clone();
+        let mut next = Vec::new();
+
+        for _ in 0..30 {
+            parallel.tick();
+            serial.tick_into(&mut next);
+            let bytes: Vec<u8> = next.iter().map(|&cell| cell as u8).collect();
+            serial = Universe::from_cells(67, 41, &bytes).unwrap();
+            assert_eq!(parallel, serial);
+        }
+    }
+}
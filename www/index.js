@@ -1,6 +1,9 @@
 import { memory } from "wasm-game-of-life/wasm_game_of_life_bg.wasm"; // Import the WebAssembly memory from the wasm-game-of-life package
 // Import the Universe class specifically from the wasm-game-of-life package
-import { Universe, Cell } from "wasm-game-of-life";
+import { Universe, init } from "wasm-game-of-life";
+
+// Report Rust panics in the console with a readable message
+init();
 
 const CELL_SIZE = 5; // Size of each cell in pixels
 const GRID_COLOR = "#CCCCCC"; // Color for the grid lines
@@ -53,9 +56,16 @@ const getIndex = (row, column) => {
   return row * width + column; // Calculate the index in the flat array representation of the universe
 }
 
+// Cells are bit-packed: cell n is bit (n % 8) of byte (n / 8)
+const bitIsSet = (n, arr) => {
+  const byte = Math.floor(n / 8);
+  const mask = 1 << (n % 8);
+  return (arr[byte] & mask) === mask;
+};
+
 const drawCells = () => {
   const cellsPtr = universe.cells();
-  const cells = new Uint8Array(memory.buffer, cellsPtr, width * height);
+  const cells = new Uint8Array(memory.buffer, cellsPtr, Math.ceil(universe.cells_len() / 8));
 
   ctx.beginPath();
 
@@ -63,9 +73,9 @@ const drawCells = () => {
     for (let col = 0; col < width; col++) {
       const idx = getIndex(row, col);
 
-      ctx.fillStyle = cells[idx] === Cell.Dead
-        ? DEAD_COLOR
-        : ALIVE_COLOR;
+      ctx.fillStyle = bitIsSet(idx, cells)
+        ? ALIVE_COLOR
+        : DEAD_COLOR;
 
       ctx.fillRect(
         col * (CELL_SIZE + 1) + 1,